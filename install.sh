@@ -15,8 +15,8 @@ cargo build --release
 mv target/release/nika $INSTALL_DIR
 mv target/release/nika-updater $INSTALL_DIR
 
-# Setup crontab to run the updater every 3 days
-(crontab -l 2>/dev/null; echo "0 12 */3 * * $INSTALL_DIR/nika-updater") | crontab -
-
 # Run the updater
 $INSTALL_DIR/nika-updater
+
+# Schedule the updater (replaces any previous entry)
+$INSTALL_DIR/nika-updater schedule install
@@ -0,0 +1,308 @@
+use once_cell::sync::Lazy;
+
+use crate::models::jmdict::Word;
+
+/// Kinds of words a deinflected term can be, as bit flags. A term without
+/// kind has not been deinflected and can be any word.
+pub type WordKinds = u8;
+
+pub const ICHIDAN: WordKinds = 1;
+pub const GODAN: WordKinds = 1 << 1;
+pub const SURU: WordKinds = 1 << 2;
+pub const KURU: WordKinds = 1 << 3;
+pub const I_ADJECTIVE: WordKinds = 1 << 4;
+/// Noun taking する, e.g. 勉強 in 勉強する.
+pub const SURU_NOUN: WordKinds = 1 << 5;
+
+/// Maximum number of rules applied to a term, e.g. 食べさせられなかった
+/// needs four of them.
+const MAX_DEPTH: usize = 6;
+
+struct Rule {
+    suffix: String,
+    replacement: &'static str,
+    /// Kinds the inflected term must be, none for the final inflections such
+    /// as the past or the polite forms.
+    kinds_in: WordKinds,
+    kinds_out: WordKinds,
+    reason: &'static str,
+}
+
+/// Godan endings: dictionary form, i-stem, a-stem, e-stem, o-stem, te form
+/// and past form.
+const GODAN_ENDINGS: [(&str, &str, &str, &str, &str, &str, &str); 9] = [
+    ("う", "い", "わ", "え", "お", "って", "った"),
+    ("く", "き", "か", "け", "こ", "いて", "いた"),
+    ("ぐ", "ぎ", "が", "げ", "ご", "いで", "いだ"),
+    ("す", "し", "さ", "せ", "そ", "して", "した"),
+    ("つ", "ち", "た", "て", "と", "って", "った"),
+    ("ぬ", "に", "な", "ね", "の", "んで", "んだ"),
+    ("ぶ", "び", "ば", "べ", "ぼ", "んで", "んだ"),
+    ("む", "み", "ま", "め", "も", "んで", "んだ"),
+    ("る", "り", "ら", "れ", "ろ", "って", "った"),
+];
+
+static RULES: Lazy<Vec<Rule>> = Lazy::new(|| {
+    let mut rules = Vec::new();
+
+    let mut add = |suffix: String, replacement, kinds_in, kinds_out, reason| {
+        rules.push(Rule {
+            suffix,
+            replacement,
+            kinds_in,
+            kinds_out,
+            reason,
+        })
+    };
+
+    // forms made from the stem of the verbs, e.g. 食べ or 書き
+    let stem_forms = |stem: &str, te: &str, ta: &str| {
+        vec![
+            (format!("{}ます", stem), 0, "polite"),
+            (format!("{}ました", stem), 0, "polite past"),
+            (format!("{}ません", stem), 0, "polite negative"),
+            (format!("{}ませんでした", stem), 0, "polite past negative"),
+            (format!("{}ましょう", stem), 0, "polite volitional"),
+            (format!("{}たい", stem), I_ADJECTIVE, "want"),
+            (format!("{}ながら", stem), 0, "while"),
+            (te.to_string(), 0, "te"),
+            (ta.to_string(), 0, "past"),
+            (format!("{}ら", ta), 0, "conditional"),
+            (format!("{}り", ta), 0, "tari"),
+            (format!("{}いる", te), ICHIDAN, "progressive"),
+            (format!("{}る", te), ICHIDAN, "progressive"),
+            (format!("{}しまう", te), GODAN, "completion"),
+        ]
+    };
+
+    for (suffix, kinds_in, reason) in stem_forms("", "て", "た") {
+        add(suffix, "る", kinds_in, ICHIDAN, reason);
+    }
+
+    for (suffix, kinds_in, reason) in [
+        ("ない", I_ADJECTIVE, "negative"),
+        ("ず", 0, "negative"),
+        ("られる", ICHIDAN, "potential or passive"),
+        ("させる", ICHIDAN, "causative"),
+        ("よう", 0, "volitional"),
+        ("れば", 0, "conditional"),
+        ("ろ", 0, "imperative"),
+    ] {
+        add(suffix.to_string(), "る", kinds_in, ICHIDAN, reason);
+    }
+
+    for (u, i, a, e, o, te, ta) in GODAN_ENDINGS {
+        for (suffix, kinds_in, reason) in stem_forms(i, te, ta) {
+            add(suffix, u, kinds_in, GODAN, reason);
+        }
+
+        for (suffix, kinds_in, reason) in [
+            (format!("{}ない", a), I_ADJECTIVE, "negative"),
+            (format!("{}ず", a), 0, "negative"),
+            (format!("{}れる", a), ICHIDAN, "passive"),
+            (format!("{}せる", a), ICHIDAN, "causative"),
+            (format!("{}る", e), ICHIDAN, "potential"),
+            (format!("{}ば", e), 0, "conditional"),
+            (e.to_string(), 0, "imperative"),
+            (format!("{}う", o), 0, "volitional"),
+        ] {
+            add(suffix, u, kinds_in, GODAN, reason);
+        }
+    }
+
+    // 行く is the only godan verb in く with a te form in って
+    add("って".into(), "く", 0, GODAN, "te");
+    add("った".into(), "く", 0, GODAN, "past");
+
+    for (suffix, kinds_in, reason) in stem_forms("し", "して", "した") {
+        add(suffix, "する", kinds_in, SURU, reason);
+    }
+
+    for (suffix, kinds_in, reason) in [
+        ("しない", I_ADJECTIVE, "negative"),
+        ("せず", 0, "negative"),
+        ("される", ICHIDAN, "passive"),
+        ("させる", ICHIDAN, "causative"),
+        ("できる", ICHIDAN, "potential"),
+        ("しよう", 0, "volitional"),
+        ("すれば", 0, "conditional"),
+        ("しろ", 0, "imperative"),
+    ] {
+        add(suffix.to_string(), "する", kinds_in, SURU, reason);
+    }
+
+    add("する".into(), "", SURU, SURU_NOUN, "suru");
+
+    for (suffix, kinds_in, reason) in stem_forms("き", "きて", "きた") {
+        add(suffix, "くる", kinds_in, KURU, reason);
+    }
+
+    for (suffix, kinds_in, reason) in [
+        ("こない", I_ADJECTIVE, "negative"),
+        ("こられる", ICHIDAN, "potential or passive"),
+        ("こさせる", ICHIDAN, "causative"),
+        ("こよう", 0, "volitional"),
+        ("くれば", 0, "conditional"),
+        ("こい", 0, "imperative"),
+    ] {
+        add(suffix.to_string(), "くる", kinds_in, KURU, reason);
+    }
+
+    for (suffix, kinds_in, reason) in [
+        ("く", 0, "adverb"),
+        ("くない", I_ADJECTIVE, "negative"),
+        ("かった", 0, "past"),
+        ("くて", 0, "te"),
+        ("ければ", 0, "conditional"),
+        ("かったら", 0, "conditional"),
+        ("さ", 0, "noun"),
+        ("そう", 0, "seemingness"),
+        ("すぎる", ICHIDAN, "excess"),
+    ] {
+        add(suffix.to_string(), "い", kinds_in, I_ADJECTIVE, reason);
+    }
+
+    rules
+});
+
+/// Term that the inflected text may come from, with the inflections undone.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Deinflection {
+    pub term: String,
+    /// Kinds the term can be, none if it is the text itself.
+    pub kinds: WordKinds,
+    /// Inflections undone, from the outermost one.
+    pub reasons: Vec<&'static str>,
+}
+
+impl Deinflection {
+    /// Whether the word, found for the term, can be inflected this way.
+    pub fn matches(&self, word: &Word) -> bool {
+        if self.kinds == 0 {
+            return true;
+        }
+
+        word.sense
+            .iter()
+            .flat_map(|sense| &sense.part_of_speech)
+            .any(|tag| kinds_of(tag) & self.kinds != 0)
+    }
+}
+
+/// Kinds of word of the JMdict part of speech tag.
+fn kinds_of(tag: &str) -> WordKinds {
+    match tag {
+        "v1" | "v1-s" => ICHIDAN,
+        // the kanji forms of 来る are conjugated like the ichidan verbs
+        "vk" => KURU | ICHIDAN,
+        "vs-i" | "vs-s" => SURU,
+        "vs" => SURU_NOUN,
+        "adj-i" | "adj-ix" => I_ADJECTIVE,
+        tag if tag.starts_with("v5") => GODAN,
+        _ => 0,
+    }
+}
+
+/// Possible dictionary forms of the text, starting with the text itself.
+pub fn deinflect(text: &str) -> Vec<Deinflection> {
+    let mut results = vec![Deinflection {
+        term: text.to_string(),
+        kinds: 0,
+        reasons: vec![],
+    }];
+
+    let mut start = 0;
+
+    for _ in 0..MAX_DEPTH {
+        let end = results.len();
+
+        for i in start..end {
+            for rule in RULES.iter() {
+                let current = &results[i];
+
+                if current.kinds != 0 && current.kinds & rule.kinds_in == 0 {
+                    continue;
+                }
+
+                let Some(stem) = current.term.strip_suffix(&rule.suffix) else {
+                    continue;
+                };
+
+                if stem.is_empty() && rule.replacement.is_empty() {
+                    continue;
+                }
+
+                let term = format!("{}{}", stem, rule.replacement);
+
+                if results
+                    .iter()
+                    .any(|r| r.term == term && r.kinds == rule.kinds_out)
+                {
+                    continue;
+                }
+
+                let mut reasons = current.reasons.clone();
+                reasons.push(rule.reason);
+
+                results.push(Deinflection {
+                    term,
+                    kinds: rule.kinds_out,
+                    reasons,
+                });
+            }
+        }
+
+        if results.len() == end {
+            break;
+        }
+
+        start = end;
+    }
+
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    fn find<'a>(deinflections: &'a [Deinflection], term: &str) -> Option<&'a Deinflection> {
+        deinflections.iter().find(|d| d.term == term)
+    }
+
+    #[test_case("食べた", "食べる", ICHIDAN, &["past"] ; "ichidan past")]
+    #[test_case("食べさせられなかった", "食べる", ICHIDAN, &["past", "negative", "potential or passive", "causative"] ; "ichidan chain")]
+    #[test_case("書いています", "書く", GODAN, &["polite", "progressive"] ; "godan progressive")]
+    #[test_case("飲んだ", "飲む", GODAN, &["past"] ; "godan past")]
+    #[test_case("行った", "行く", GODAN, &["past"] ; "iku past")]
+    #[test_case("勉強しない", "勉強", SURU_NOUN, &["negative", "suru"] ; "suru noun")]
+    #[test_case("こない", "くる", KURU, &["negative"] ; "kuru")]
+    #[test_case("高くなかった", "高い", I_ADJECTIVE, &["past", "negative"] ; "adjective")]
+    #[test_case("食べたい", "食べる", ICHIDAN, &["want"] ; "want")]
+    fn test_deinflect(text: &str, term: &str, kinds: WordKinds, reasons: &[&str]) {
+        let deinflections = deinflect(text);
+        let deinflection = find(&deinflections, term).unwrap();
+
+        assert_eq!(deinflection.kinds & kinds, kinds);
+        assert_eq!(deinflection.reasons, reasons);
+    }
+
+    #[test]
+    fn test_deinflect_itself() {
+        let deinflections = deinflect("日本");
+
+        assert_eq!(deinflections[0].term, "日本");
+        assert_eq!(deinflections[0].kinds, 0);
+        assert!(find(&deinflections, "").is_none());
+    }
+
+    #[test_case("v1", ICHIDAN)]
+    #[test_case("v5k-s", GODAN)]
+    #[test_case("vk", KURU | ICHIDAN)]
+    #[test_case("adj-na", 0)]
+    fn test_kinds_of(tag: &str, expected: WordKinds) {
+        assert_eq!(kinds_of(tag), expected);
+    }
+}
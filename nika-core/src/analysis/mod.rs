@@ -0,0 +1,2 @@
+pub mod deinflection;
+pub mod tokenizer;
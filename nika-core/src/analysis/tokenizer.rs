@@ -0,0 +1,246 @@
+use std::collections::HashMap;
+
+use super::deinflection::deinflect;
+use super::deinflection::Deinflection;
+use crate::models::jmdict::Word;
+use crate::utils::japanese::JapaneseCharacter;
+use crate::utils::japanese::JapaneseString;
+
+/// Longest text looked up in the dictionary, in characters.
+const MAX_TOKEN_LENGTH: usize = 16;
+
+/// Part of a sentence, with the word it was found as, if any.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token<'a> {
+    pub surface: String,
+    pub word: Option<&'a Word>,
+    /// Form of the word found, with the inflections undone.
+    pub dictionary_form: String,
+    /// Inflections undone, from the outermost one.
+    pub reasons: Vec<&'static str>,
+}
+
+impl Token<'_> {
+    /// Reading of the dictionary form, the kana applying to it.
+    pub fn reading(&self) -> Option<&str> {
+        let word = self.word?;
+
+        if word.kana.iter().any(|k| k.text == self.dictionary_form) {
+            return Some(&self.dictionary_form);
+        }
+
+        word.kana
+            .iter()
+            .find(|k| {
+                k.applies_to_kanji
+                    .iter()
+                    .any(|a| a == "*" || *a == self.dictionary_form)
+            })
+            .map(|k| k.text.as_str())
+    }
+}
+
+/// Splits the sentences into the longest words of the dictionary, undoing
+/// their inflections.
+pub struct Tokenizer<'a> {
+    forms: HashMap<&'a str, Vec<&'a Word>>,
+}
+
+impl<'a> Tokenizer<'a> {
+    pub fn new<I: IntoIterator<Item = &'a Word>>(words: I) -> Self {
+        let mut forms = HashMap::<&str, Vec<&Word>>::new();
+
+        for word in words {
+            let texts = word
+                .kanji
+                .iter()
+                .map(|k| k.text.as_str())
+                .chain(word.kana.iter().map(|k| k.text.as_str()));
+
+            for text in texts {
+                let words = forms.entry(text).or_default();
+
+                if !words.iter().any(|w| w.id == word.id) {
+                    words.push(word);
+                }
+            }
+        }
+
+        for words in forms.values_mut() {
+            words.sort_by(|a, b| a.id.cmp(&b.id));
+        }
+
+        Self { forms }
+    }
+
+    pub fn tokenize(&self, text: &str) -> Vec<Token<'a>> {
+        let chars = text.chars().collect::<Vec<char>>();
+        let mut tokens = Vec::<Token>::new();
+        let mut start = 0;
+
+        while start < chars.len() {
+            if !is_japanese(chars[start]) {
+                let end = (start..chars.len())
+                    .find(|&i| is_japanese(chars[i]))
+                    .unwrap_or(chars.len());
+
+                let surface = chars[start..end].iter().collect::<String>();
+
+                if !surface.trim().is_empty() {
+                    tokens.push(unknown(surface.trim()));
+                }
+
+                start = end;
+                continue;
+            }
+
+            let longest = (start + MAX_TOKEN_LENGTH).min(chars.len());
+
+            let token = (start + 1..=longest).rev().find_map(|end| {
+                let surface = chars[start..end].iter().collect::<String>();
+                self.lookup(&surface).map(|token| (end, token))
+            });
+
+            match token {
+                Some((end, token)) => {
+                    tokens.push(token);
+                    start = end;
+                }
+                None => {
+                    tokens.push(unknown(&chars[start].to_string()));
+                    start += 1;
+                }
+            }
+        }
+
+        tokens
+    }
+
+    /// Most likely word of the text: the least inflected one, preferring the
+    /// words written this way and the common ones.
+    fn lookup(&self, surface: &str) -> Option<Token<'a>> {
+        deinflect(surface)
+            .into_iter()
+            .flat_map(|deinflection| {
+                self.forms
+                    .get(deinflection.term.as_str())
+                    .into_iter()
+                    .flatten()
+                    .filter(|word| deinflection.matches(word))
+                    .map(|word| (deinflection.clone(), *word))
+                    .collect::<Vec<(Deinflection, &Word)>>()
+            })
+            .min_by_key(|(deinflection, word)| {
+                (
+                    deinflection.reasons.len(),
+                    !is_primary_form(word, &deinflection.term),
+                    !word.is_common(),
+                )
+            })
+            .map(|(deinflection, word)| Token {
+                surface: surface.to_string(),
+                word: Some(word),
+                dictionary_form: deinflection.term,
+                reasons: deinflection.reasons,
+            })
+    }
+}
+
+fn unknown(surface: &str) -> Token<'static> {
+    Token {
+        surface: surface.to_string(),
+        word: None,
+        dictionary_form: surface.to_string(),
+        reasons: vec![],
+    }
+}
+
+fn is_japanese(c: char) -> bool {
+    c.is_kanji() || c.is_kana() || c.is_prolonged_sound_mark()
+}
+
+/// Whether the text is how the word is usually written: its first kanji form,
+/// or its first reading when it is written in kana.
+fn is_primary_form(word: &Word, text: &str) -> bool {
+    let usually_kana = word
+        .sense
+        .first()
+        .is_some_and(|sense| sense.misc.iter().any(|tag| tag == "uk"));
+
+    match word.kanji.first() {
+        Some(kanji) if !usually_kana || text.has_kanji() => kanji.text == text,
+        _ => word.kana.first().is_some_and(|kana| kana.text == text),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+
+    use super::*;
+    use crate::models::jmdict::JMdict;
+
+    fn words() -> Vec<Word> {
+        let fixtures_path = Path::new("tests").join("fixtures").join("words.json");
+        let words = fs::read_to_string(fixtures_path).unwrap();
+
+        serde_json::from_str::<JMdict>(&words).unwrap().words
+    }
+
+    fn surfaces<'a>(tokens: &'a [Token]) -> Vec<(&'a str, Option<&'a str>)> {
+        tokens
+            .iter()
+            .map(|t| (t.surface.as_str(), t.word.map(|w| w.id.as_str())))
+            .collect()
+    }
+
+    #[test]
+    fn test_tokenize() {
+        let words = words();
+        let tokenizer = Tokenizer::new(&words);
+
+        let tokens = tokenizer.tokenize("日本として、自由に食べました。");
+
+        assert_eq!(
+            surfaces(&tokens),
+            vec![
+                ("日本", Some("1582710")),
+                ("として", Some("1008590")),
+                ("、", None),
+                ("自由", Some("1318720")),
+                ("に", None),
+                ("食べました", Some("1358280")),
+                ("。", None),
+            ]
+        );
+
+        assert_eq!(tokens[5].dictionary_form, "食べる");
+        assert_eq!(tokens[5].reasons, vec!["polite past"]);
+        assert_eq!(tokens[5].reading(), Some("たべる"));
+    }
+
+    #[test]
+    fn test_tokenize_other_scripts() {
+        let words = words();
+        let tokenizer = Tokenizer::new(&words);
+
+        let tokens = tokenizer.tokenize("NIKA 日本");
+
+        assert_eq!(
+            surfaces(&tokens),
+            vec![("NIKA", None), ("日本", Some("1582710"))]
+        );
+    }
+
+    #[test]
+    fn test_reading_of_kana_form() {
+        let words = words();
+        let tokenizer = Tokenizer::new(&words);
+
+        let tokens = tokenizer.tokenize("にっぽん");
+
+        assert_eq!(tokens[0].word.map(|w| w.id.as_str()), Some("1582710"));
+        assert_eq!(tokens[0].reading(), Some("にっぽん"));
+    }
+}
@@ -0,0 +1,84 @@
+use std::collections::HashMap;
+
+use crate::analysis::tokenizer::Tokenizer;
+use crate::errors::Result;
+use crate::models::jmdict::Word;
+use crate::models::study_list::ItemStatus;
+use crate::models::study_list::StudyList;
+use crate::repositories::config_repository::ConfigRepository;
+use crate::repositories::dictionary_repository::DictionaryRepository;
+use crate::repositories::list_repository::ListRepository;
+
+pub struct AnalysisController {
+    dictionary_repository: DictionaryRepository,
+    config_repository: ConfigRepository,
+    list_repository: ListRepository,
+}
+
+impl AnalysisController {
+    pub fn new(
+        dictionary_repository: DictionaryRepository,
+        config_repository: ConfigRepository,
+        list_repository: ListRepository,
+    ) -> Self {
+        Self {
+            dictionary_repository,
+            config_repository,
+            list_repository,
+        }
+    }
+
+    /// Tokenizer over the words of the dictionary, to reuse across the
+    /// sentences.
+    pub fn tokenizer(&self) -> Tokenizer<'_> {
+        Tokenizer::new(self.dictionary_repository.iter_words())
+    }
+
+    /// Words of the text not marked as known in any study list, with their
+    /// number of occurrences, from the most frequent one.
+    pub fn unknown_words(&self, text: &str) -> Result<Vec<(&Word, usize)>> {
+        let known = self.list_repository.items_with_status(ItemStatus::Known)?;
+        let tokenizer = self.tokenizer();
+
+        let mut counts = HashMap::<&str, usize>::new();
+        let mut words = Vec::<&Word>::new();
+
+        for token in text.lines().flat_map(|line| tokenizer.tokenize(line)) {
+            let Some(word) = token.word else {
+                continue;
+            };
+
+            if known.contains(&word.id) {
+                continue;
+            }
+
+            let count = counts.entry(&word.id).or_default();
+
+            if *count == 0 {
+                words.push(word);
+            }
+
+            *count += 1;
+        }
+
+        let mut unknown = words
+            .into_iter()
+            .map(|word| (word, counts[word.id.as_str()]))
+            .collect::<Vec<(&Word, usize)>>();
+
+        // the words found as many times are kept in order of appearance
+        unknown.sort_by(|(_, a), (_, b)| b.cmp(a));
+
+        Ok(unknown)
+    }
+
+    /// Creates a study list with the words, selecting it when there are no
+    /// other lists.
+    pub fn create_list(&self, name: &str, items: Vec<String>) -> Result<()> {
+        super::create_list(
+            &self.list_repository,
+            &self.config_repository,
+            StudyList::new(name, items),
+        )
+    }
+}
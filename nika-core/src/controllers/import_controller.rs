@@ -0,0 +1,91 @@
+use rayon::prelude::*;
+
+use crate::errors::Result;
+use crate::importer::matching::Candidate;
+use crate::importer::matching::MatchResult;
+use crate::importer::matching::MatchStatus;
+use crate::importer::matching::Matcher;
+use crate::importer::matching::QueryResult;
+use crate::importer::query::Query;
+use crate::models::study_list::StudyList;
+use crate::repositories::config_repository::ConfigRepository;
+use crate::repositories::list_repository::ListRepository;
+
+pub struct ImportController {
+    matcher: Matcher,
+    config_repository: ConfigRepository,
+    list_repository: ListRepository,
+}
+
+impl ImportController {
+    pub fn new(
+        matcher: Matcher,
+        config_repository: ConfigRepository,
+        list_repository: ListRepository,
+    ) -> Self {
+        Self {
+            matcher,
+            config_repository,
+            list_repository,
+        }
+    }
+
+    /// Matches each query to the dictionary words, calling `on_progress`
+    /// after each query. The most likely candidate is accepted when its
+    /// confidence is at least `min_confidence`, otherwise the candidates are
+    /// reported as a conflict from the most likely one.
+    pub fn find_matches<F>(
+        &self,
+        queries: &[Query],
+        min_confidence: f64,
+        on_progress: F,
+    ) -> MatchResult
+    where
+        F: Fn() + Sync,
+    {
+        let results = queries
+            .par_iter()
+            .enumerate()
+            .map(|(index, query)| {
+                let candidates = self.matcher.rank(query, None, None);
+                let confidence = candidates.first().map_or(0.0, |c| c.confidence);
+
+                let status = match candidates.as_slice() {
+                    [] => MatchStatus::Missing,
+                    [best, ..] if best.confidence >= min_confidence => {
+                        MatchStatus::Match(best.word.id.clone())
+                    }
+                    _ => MatchStatus::Conflict(
+                        candidates.iter().map(|c| c.word.id.clone()).collect(),
+                    ),
+                };
+
+                on_progress();
+
+                QueryResult {
+                    index,
+                    query: query.clone(),
+                    status,
+                    confidence,
+                }
+            })
+            .collect();
+
+        MatchResult { results }
+    }
+
+    /// Returns the candidate words of a query with their score.
+    pub fn candidates(&self, query: &Query, ids: &[String]) -> Vec<Candidate<'_>> {
+        self.matcher.candidates(query, ids)
+    }
+
+    /// Creates a study list with the matched words, selecting it when there
+    /// are no other lists.
+    pub fn create_list(&self, name: &str, items: Vec<String>) -> Result<()> {
+        super::create_list(
+            &self.list_repository,
+            &self.config_repository,
+            StudyList::new(name, items),
+        )
+    }
+}
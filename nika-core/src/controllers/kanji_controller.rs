@@ -0,0 +1,47 @@
+use crate::errors::KanjiError;
+use crate::errors::Result;
+use crate::models::kanjidic::Kanji;
+use crate::models::kanjidic::KanjiCode;
+use crate::repositories::dictionary_repository::DictionaryRepository;
+
+pub struct KanjiController {
+    dictionary_repository: DictionaryRepository,
+}
+
+impl KanjiController {
+    pub fn new(dictionary_repository: DictionaryRepository) -> Self {
+        Self {
+            dictionary_repository,
+        }
+    }
+
+    pub fn kanji(&self, literal: &str) -> Option<&Kanji> {
+        self.dictionary_repository.kanji(literal)
+    }
+
+    /// Kanji with the code, e.g. a SKIP code or a Heisig index.
+    pub fn kanji_with_code(&self, code: &KanjiCode) -> Vec<&Kanji> {
+        self.dictionary_repository.kanji_with_code(code)
+    }
+
+    pub fn strokes(&self, literal: &str) -> &[String] {
+        self.dictionary_repository.strokes(literal)
+    }
+
+    pub fn components(&self, literal: &str) -> Vec<&str> {
+        self.dictionary_repository.components(literal)
+    }
+
+    /// Kanji containing all the radicals, from the ones with the fewest
+    /// strokes.
+    pub fn kanji_with_radicals(&self, radicals: &[&str]) -> Result<Vec<&str>> {
+        if let Some(radical) = radicals
+            .iter()
+            .find(|radical| !self.dictionary_repository.is_radical(radical))
+        {
+            return Err(KanjiError::UnknownRadical(radical.to_string()).into());
+        }
+
+        Ok(self.dictionary_repository.kanji_with_radicals(radicals))
+    }
+}
@@ -1,3 +1,29 @@
+pub mod analysis_controller;
+pub mod import_controller;
+pub mod kanji_controller;
 pub mod random_controller;
 pub mod search_controller;
 pub mod study_controller;
+
+use crate::errors::Result;
+use crate::models::study_list::StudyList;
+use crate::repositories::config_repository::ConfigRepository;
+use crate::repositories::list_repository::ListRepository;
+
+/// Adds the study list, selecting it when there are no other lists.
+fn create_list(
+    list_repository: &ListRepository,
+    config_repository: &ConfigRepository,
+    study_list: StudyList,
+) -> Result<()> {
+    let is_empty = list_repository.get_lists()?.is_empty();
+    let name = study_list.name.clone();
+
+    list_repository.add_list(study_list)?;
+
+    if is_empty {
+        config_repository.set_current_list(&name)?;
+    }
+
+    Ok(())
+}
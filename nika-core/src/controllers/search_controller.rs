@@ -1,5 +1,9 @@
+use crate::importer::tatoeba::Example;
 use crate::models::jmdict::Word;
+use crate::models::jmdict::Xref;
+use crate::models::kanjidic::Kanji;
 use crate::repositories::dictionary_repository::DictionaryRepository;
+use crate::repositories::dictionary_repository::SimilarWords;
 
 pub struct SearchController {
     dictionary_repository: DictionaryRepository,
@@ -15,4 +19,34 @@ impl SearchController {
     pub fn search(&self, query: &str, common: Option<bool>) -> Vec<Word> {
         self.dictionary_repository.search(query, common)
     }
+
+    /// Example sentences of the word, the best ones first.
+    pub fn examples(&self, id: &str) -> &[Example] {
+        self.dictionary_repository.examples(id)
+    }
+
+    /// Words written with the text, from the common ones.
+    pub fn words_with_form(&self, text: &str) -> Vec<&Word> {
+        self.dictionary_repository.words_with_form(text)
+    }
+
+    pub fn similar(&self, id: &str) -> Option<SimilarWords<'_>> {
+        self.dictionary_repository.similar(id)
+    }
+
+    /// Kanjidic characters of the kanji of the usual form of the word.
+    pub fn kanji_breakdown(&self, word: &Word) -> Vec<&Kanji> {
+        word.kanji
+            .first()
+            .map(|kanji| self.dictionary_repository.kanji_of(&kanji.text))
+            .unwrap_or_default()
+    }
+
+    /// Most common word the cross-reference points to.
+    pub fn resolve_xref(&self, xref: &Xref) -> Option<&Word> {
+        self.dictionary_repository
+            .resolve_xref(xref)
+            .first()
+            .copied()
+    }
 }
@@ -1,8 +1,19 @@
+use std::ops::Range;
+
+use crate::errors::KanjiError;
+use crate::errors::NikaError;
 use crate::errors::Result;
+use crate::errors::StudyListError;
+use crate::importer::tatoeba::Example;
+use crate::models::jmdict::Language3Letter;
 use crate::models::jmdict::Word;
+use crate::models::jmdict::Xref;
+use crate::models::kanjidic::Kanji;
 use crate::models::link::Link;
+use crate::models::study_list::ItemStatus;
 use crate::models::study_list::StudyConfig;
 use crate::models::study_list::StudyList;
+use crate::models::user_config::DisplaySettings;
 use crate::repositories::config_repository::ConfigRepository;
 use crate::repositories::dictionary_repository::DictionaryRepository;
 use crate::repositories::list_repository::ListRepository;
@@ -44,23 +55,29 @@ impl StudyController {
 
     pub fn study_words(&self, name: &str, daily: bool) -> Result<Vec<&Word>> {
         let study_list = self.list_repository.get_list(name)?;
-
-        let index = study_list.config.current_index;
-        let count = study_list.config.items_per_day;
-
-        let id_list = study_list.items.iter();
-
-        let ids: Vec<&str> = if !daily {
-            id_list.map(AsRef::as_ref).collect()
-        } else {
-            id_list.skip(index).take(count).map(AsRef::as_ref).collect()
-        };
+        let ids = study_items(&study_list, daily);
 
         let words = self.dictionary_repository.words(&ids);
 
         Ok(words)
     }
 
+    /// Kanjidic characters of a kanji list, all of them or the ones of the
+    /// day. Fails when a kanji is missing from Kanjidic, e.g. when the kanji
+    /// data was not generated.
+    pub fn study_kanji(&self, name: &str, daily: bool) -> Result<Vec<&Kanji>> {
+        let study_list = self.list_repository.get_list(name)?;
+
+        study_items(&study_list, daily)
+            .iter()
+            .map(|literal| {
+                self.dictionary_repository
+                    .kanji(literal)
+                    .ok_or_else(|| KanjiError::UnknownKanji(literal.to_string()).into())
+            })
+            .collect()
+    }
+
     pub fn select(&self, name: &str) -> Result<()> {
         match self.list_repository.get_list(name) {
             Ok(_) => self.config_repository.set_current_list(name),
@@ -83,7 +100,106 @@ impl StudyController {
         self.list_repository.update_list_config(name, config)
     }
 
+    /// Marks the item of the list, given by id or by any of its forms, with
+    /// the status, or as new without status. Returns the id of the item.
+    pub fn mark(&self, name: &str, item: &str, status: Option<ItemStatus>) -> Result<String> {
+        let study_list = self.list_repository.get_list(name)?;
+
+        let id = study_list
+            .items
+            .iter()
+            .find(|id| {
+                *id == item
+                    || self.dictionary_repository.word(id).is_some_and(|word| {
+                        word.kanji.iter().any(|k| k.text == item)
+                            || word.kana.iter().any(|k| k.text == item)
+                    })
+            })
+            .ok_or(NikaError::List(StudyListError::ItemNotFound))?;
+
+        self.list_repository.set_item_status(name, id, status)?;
+
+        Ok(id.clone())
+    }
+
     pub fn get_links(&self) -> Result<Vec<Link>> {
         self.config_repository.dictionaries()
     }
+
+    /// Kanji of the school grade (1 to 6, 8 for the rest of the Jōyō kanji),
+    /// from the most frequent one.
+    pub fn grade_items(&self, grade: u16) -> Vec<String> {
+        self.dictionary_repository
+            .kanji_with_grade(grade)
+            .iter()
+            .map(|kanji| kanji.literal.clone())
+            .collect()
+    }
+
+    /// Example sentences of the word, the best ones first.
+    pub fn examples(&self, id: &str) -> &[Example] {
+        self.dictionary_repository.examples(id)
+    }
+
+    /// Kanjidic characters of the kanji of the usual form of the word.
+    pub fn kanji_breakdown(&self, word: &Word) -> Vec<&Kanji> {
+        word.kanji
+            .first()
+            .map(|kanji| self.dictionary_repository.kanji_of(&kanji.text))
+            .unwrap_or_default()
+    }
+
+    /// Most common word the cross-reference points to.
+    pub fn resolve_xref(&self, xref: &Xref) -> Option<&Word> {
+        self.dictionary_repository
+            .resolve_xref(xref)
+            .first()
+            .copied()
+    }
+
+    /// Words read like the word but written with other kanji, which may be
+    /// confused with it.
+    pub fn homophones(&self, id: &str) -> Vec<&Word> {
+        self.dictionary_repository
+            .homophones(id)
+            .into_iter()
+            .filter(|word| !word.kanji.is_empty())
+            .collect()
+    }
+
+    pub fn has_frequencies(&self) -> bool {
+        self.dictionary_repository.has_frequencies()
+    }
+
+    /// Ids of the dictionary words ranked within the range, from the most
+    /// frequent one.
+    pub fn frequency_items(&self, ranks: Range<u32>) -> Vec<String> {
+        self.dictionary_repository
+            .frequent_words(ranks)
+            .iter()
+            .map(|word| word.id.clone())
+            .collect()
+    }
+
+    pub fn display_settings(&self) -> Result<DisplaySettings> {
+        self.config_repository.display_settings()
+    }
+
+    pub fn languages(&self) -> Result<Vec<Language3Letter>> {
+        self.config_repository.languages()
+    }
+}
+
+/// Items of the list, all of them or the ones of the day.
+fn study_items(study_list: &StudyList, daily: bool) -> Vec<&str> {
+    let index = study_list.config.current_index;
+    let count = study_list.config.items_per_day;
+
+    let items = study_list.items.iter();
+
+    if !daily {
+        items.map(AsRef::as_ref).collect()
+    } else {
+        items.skip(index).take(count).map(AsRef::as_ref).collect()
+    }
 }
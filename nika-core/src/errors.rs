@@ -19,6 +19,10 @@ pub enum NikaError {
     Bincode(#[from] BincodeError),
     #[error("List error: {0}")]
     List(#[from] StudyListError),
+    #[error("Import error: {0}")]
+    Import(#[from] ImportError),
+    #[error("Kanji error: {0}")]
+    Kanji(#[from] KanjiError),
     #[error("Config error: {0}")]
     ConfigSerialization(#[from] TomlSerError),
     #[error("Config error: {0}")]
@@ -31,4 +35,22 @@ pub enum StudyListError {
     ListNotFound,
     #[error("List already exists")]
     ListAlreadyExists,
+    #[error("Item not found in the list")]
+    ItemNotFound,
+}
+
+#[derive(Error, Debug)]
+pub enum ImportError {
+    #[error("Invalid CSV at line {0}: {1}")]
+    InvalidCsv(usize, String),
+    #[error("Invalid TSV at line {0}: {1}")]
+    InvalidTsv(usize, String),
+}
+
+#[derive(Error, Debug)]
+pub enum KanjiError {
+    #[error("Unknown radical {0}")]
+    UnknownRadical(String),
+    #[error("Kanji {0} not found, the kanji data may be missing (run `nika-updater`)")]
+    UnknownKanji(String),
 }
@@ -0,0 +1,219 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use crate::errors::ImportError;
+use crate::errors::Result;
+use crate::models::jmdict::Word;
+use crate::utils::japanese::JapaneseString;
+
+/// Pitch accent patterns, keyed by the written form and the reading in
+/// hiragana.
+pub type AccentMap = HashMap<(String, String), Vec<u8>>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccentEntry {
+    pub text: String,
+    pub reading: String,
+    /// Mora after which the pitch drops, 0 when it does not drop.
+    pub patterns: Vec<u8>,
+}
+
+/// Parses an accent dictionary with the written form, the reading (empty for
+/// the kana words) and the comma separated accent patterns of each word,
+/// separated by tabs, as the Kanjium `accents.txt`. The part of speech before
+/// a pattern (e.g. `(名)0`) is left out.
+pub fn parse_tsv(content: &str) -> Result<Vec<AccentEntry>> {
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.starts_with('#'))
+        .filter_map(|(i, line)| {
+            let fields = line.split('\t').map(str::trim).collect::<Vec<&str>>();
+
+            let [text, reading, patterns] = fields[..] else {
+                return Some(Err(ImportError::InvalidTsv(
+                    i + 1,
+                    "expected 3 columns".into(),
+                )
+                .into()));
+            };
+
+            let parsed = patterns
+                .split(',')
+                .map(|pattern| {
+                    pattern
+                        .trim_start_matches(|c: char| !c.is_ascii_digit())
+                        .parse::<u8>()
+                })
+                .collect::<std::result::Result<Vec<u8>, _>>();
+
+            match parsed {
+                Ok(mut parsed) if !text.is_empty() => {
+                    dedup(&mut parsed);
+
+                    Some(Ok(AccentEntry {
+                        text: text.to_string(),
+                        reading: if reading.is_empty() { text } else { reading }.to_string(),
+                        patterns: parsed,
+                    }))
+                }
+                Ok(_) => Some(Err(ImportError::InvalidTsv(
+                    i + 1,
+                    "empty written form".into(),
+                )
+                .into())),
+                Err(_) if i == 0 => None,
+                Err(_) => Some(Err(ImportError::InvalidTsv(
+                    i + 1,
+                    format!("invalid accent patterns '{}'", patterns),
+                )
+                .into())),
+            }
+        })
+        .collect()
+}
+
+/// Groups the patterns of the entries, merging the ones found more than once.
+pub fn accent_map(entries: &[AccentEntry]) -> AccentMap {
+    let mut accents = AccentMap::new();
+
+    for entry in entries {
+        let patterns = accents
+            .entry((entry.text.clone(), entry.reading.to_hiragana()))
+            .or_default();
+
+        patterns.extend(&entry.patterns);
+        dedup(patterns);
+    }
+
+    accents
+}
+
+/// Sets the accent patterns of the kana readings of the words, matching the
+/// kanji the reading applies to, or the reading itself when it is not written
+/// with kanji. Returns the number of readings with patterns.
+pub fn attach_accents(words: &mut [Word], accents: &AccentMap) -> usize {
+    let mut count = 0;
+
+    for word in words {
+        let kanji = word
+            .kanji
+            .iter()
+            .map(|kanji| kanji.text.clone())
+            .collect::<Vec<String>>();
+
+        for kana in &mut word.kana {
+            let mut texts = kanji
+                .iter()
+                .filter(|text| kana.applies_to_kanji.iter().any(|a| a == "*" || a == *text))
+                .collect::<Vec<&String>>();
+
+            if texts.is_empty() {
+                texts.push(&kana.text);
+            }
+
+            let reading = kana.text.to_hiragana();
+
+            let patterns = texts
+                .into_iter()
+                .find_map(|text| accents.get(&(text.clone(), reading.clone())));
+
+            if let Some(patterns) = patterns {
+                kana.pitch_accents = patterns.clone();
+                count += 1;
+            }
+        }
+    }
+
+    count
+}
+
+/// Removes the repeated patterns, keeping the first ones.
+fn dedup(patterns: &mut Vec<u8>) {
+    let mut seen = HashSet::new();
+    patterns.retain(|pattern| seen.insert(*pattern));
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+
+    use test_case::test_case;
+
+    use super::*;
+    use crate::models::jmdict::JMdict;
+
+    #[test]
+    fn test_parse_tsv() {
+        let content = "箸\tはし\t1\n橋\tはし\t2\nちょっと\t\t1,0\n";
+
+        let entries = parse_tsv(content).unwrap();
+
+        assert_eq!(entries.len(), 3);
+        assert_eq!(
+            entries[2],
+            AccentEntry {
+                text: "ちょっと".into(),
+                reading: "ちょっと".into(),
+                patterns: vec![1, 0],
+            }
+        );
+    }
+
+    #[test_case("(名)0,(副)1", vec![0, 1] ; "part of speech")]
+    #[test_case("2,2", vec![2] ; "repeated")]
+    fn test_parse_patterns(patterns: &str, expected: Vec<u8>) {
+        let entries = parse_tsv(&format!("一寸\tちょっと\t{}", patterns)).unwrap();
+
+        assert_eq!(entries[0].patterns, expected);
+    }
+
+    #[test]
+    fn test_parse_tsv_skips_header() {
+        let entries = parse_tsv("word\treading\taccent\n橋\tはし\t2").unwrap();
+
+        assert_eq!(entries.len(), 1);
+    }
+
+    #[test_case("橋\tはし" ; "missing column")]
+    #[test_case("橋\tはし\t2\n箸\tはし\tx" ; "invalid pattern")]
+    #[test_case("橋\tはし\t2\n\tはし\t1" ; "empty written form")]
+    fn test_parse_tsv_invalid(content: &str) {
+        assert!(parse_tsv(content).is_err());
+    }
+
+    #[test]
+    fn test_accent_map_katakana_reading() {
+        let entries = parse_tsv("アイス\t\t1").unwrap();
+
+        assert_eq!(
+            accent_map(&entries)[&("アイス".to_string(), "あいす".to_string())],
+            vec![1]
+        );
+    }
+
+    #[test]
+    fn test_attach_accents() {
+        let fixtures_path = Path::new("tests").join("fixtures").join("words.json");
+        let words = fs::read_to_string(fixtures_path).unwrap();
+        let mut data: JMdict = serde_json::from_str(&words).unwrap();
+
+        let entries = parse_tsv("日本\tにほん\t2\n日本\tにっぽん\t3\nとして\t\t(助)0\n").unwrap();
+
+        let count = attach_accents(&mut data.words, &accent_map(&entries));
+        let kana = |text: &str| {
+            data.words
+                .iter()
+                .flat_map(|word| &word.kana)
+                .find(|kana| kana.text == text)
+                .unwrap()
+        };
+
+        assert_eq!(count, 3);
+        assert_eq!(kana("にほん").pitch_accents, vec![2]);
+        assert_eq!(kana("にっぽん").pitch_accents, vec![3]);
+        assert_eq!(kana("として").pitch_accents, vec![0]);
+        assert!(kana("たべる").pitch_accents.is_empty());
+    }
+}
@@ -0,0 +1,315 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use once_cell::sync::Lazy;
+use rayon::prelude::*;
+
+use crate::models::jmdict::Word;
+
+/// Context words of each sense of the words, keyed by word id.
+pub type SenseMap = HashMap<String, Vec<HashSet<String>>>;
+
+static LEMMAS: Lazy<HashMap<&str, &str>> = Lazy::new(|| {
+    include_str!("../../data/lemmas.tsv")
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| line.split_once('\t'))
+        .collect()
+});
+
+static STOP_WORDS: Lazy<HashSet<&str>> = Lazy::new(|| {
+    include_str!("../../data/stopwords.txt")
+        .lines()
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .collect()
+});
+
+/// Gloss abbreviations and leftovers of the contractions (e.g. "it's").
+const EXCLUDED_WORDS: [&str; 4] = ["ie", "eg", "s", "t"];
+
+/// Extracts the context words of the senses of each word.
+pub fn extract_context_words(words: &[Word]) -> SenseMap {
+    words
+        .par_iter()
+        .map(|word| (word.id.clone(), sense_context_words(word)))
+        .collect()
+}
+
+/// Extracts the context words of the English glosses of each word sense, in
+/// order. The senses without English glosses have no context words.
+pub fn sense_context_words(word: &Word) -> Vec<HashSet<String>> {
+    word.sense
+        .iter()
+        .map(|sense| {
+            let text = sense
+                .gloss
+                .iter()
+                .filter(|gloss| gloss.lang == "eng")
+                .map(|gloss| gloss.text.as_str())
+                .collect::<Vec<&str>>()
+                .join(". ");
+
+            context_words(&text)
+        })
+        .collect()
+}
+
+/// Extracts the context words from an English text.
+///
+/// - Remove e.g. examples in the brackets
+/// - Keep only ascii characters
+/// - Remove punctuation
+/// - Convert to lower case
+/// - Lemmatize the words
+/// - Exclude stop words, abbreviations (e.g. 'ie', 'eg') and empty strings
+pub fn context_words(text: &str) -> HashSet<String> {
+    let text = remove_examples(text)
+        .replace("i.e.", " ie ")
+        .replace("e.g.", " eg ");
+
+    text.split(|c: char| !c.is_ascii_alphanumeric() && c != '\'')
+        .map(|token| token.replace('\'', "").to_lowercase())
+        .filter(|token| !token.is_empty())
+        .map(|token| lemmatize(&token))
+        .filter(|lemma| !EXCLUDED_WORDS.contains(&lemma.as_str()))
+        .filter(|lemma| !STOP_WORDS.contains(lemma.as_str()))
+        .collect()
+}
+
+/// Removes the examples from "e.g." up to the closing bracket.
+fn remove_examples(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find("e.g.") {
+        match rest[start..].find(')') {
+            Some(end) => {
+                result.push_str(&rest[..start]);
+                rest = &rest[start + end..];
+            }
+            None => break,
+        }
+    }
+
+    result.push_str(rest);
+    result
+}
+
+/// Reduces a lower case word to its lemma using the bundled table of
+/// irregular forms, falling back to the English suffix rules.
+pub fn lemmatize(word: &str) -> String {
+    if let Some(lemma) = LEMMAS.get(word) {
+        return lemma.to_string();
+    }
+
+    if word.len() <= 3 || !word.chars().all(|c| c.is_ascii_lowercase()) {
+        return word.to_string();
+    }
+
+    strip_inflection(&strip_plural(word))
+}
+
+/// Removes the plural (or third person) "-s" suffix.
+fn strip_plural(word: &str) -> String {
+    if let Some(stem) = word.strip_suffix("ies") {
+        if stem.len() > 1 {
+            return format!("{}y", stem);
+        }
+    }
+
+    if let Some(stem) = word.strip_suffix("es") {
+        if ["ss", "x", "ch", "sh"].iter().any(|s| stem.ends_with(s)) {
+            return stem.to_string();
+        }
+    }
+
+    if ["ss", "us", "is"].iter().any(|s| word.ends_with(s)) {
+        return word.to_string();
+    }
+
+    word.strip_suffix('s').unwrap_or(word).to_string()
+}
+
+/// Removes the "-ed" and "-ing" suffixes, restoring the final "e" or removing
+/// the doubled consonant of the stem.
+fn strip_inflection(word: &str) -> String {
+    if let Some(stem) = word.strip_suffix("eed") {
+        return if measure(stem) > 0 {
+            format!("{}ee", stem)
+        } else {
+            word.to_string()
+        };
+    }
+
+    if let Some(stem) = word.strip_suffix("ied") {
+        if stem.len() > 1 {
+            return format!("{}y", stem);
+        }
+    }
+
+    let stem = word
+        .strip_suffix("ed")
+        .or_else(|| word.strip_suffix("ing"))
+        .filter(|stem| stem.chars().any(|c| is_vowel(c) || c == 'y'));
+
+    let Some(stem) = stem else {
+        return word.to_string();
+    };
+
+    let chars = stem.chars().collect::<Vec<char>>();
+
+    // e.g. "complicat(ed)" and "recycl(ing)" but not "eat(ing)" or "curl(ed)"
+    let restores_e = match chars.as_slice() {
+        [.., c, 'a', 't'] => !is_vowel(*c),
+        [.., c, 'l'] => ['b', 'c', 'd', 'f', 'g', 'k', 'p', 't', 'z'].contains(c),
+        [.., 'i', 'z'] => true,
+        _ => false,
+    };
+
+    if restores_e {
+        return format!("{}e", stem);
+    }
+
+    if let [.., a, b] = chars.as_slice() {
+        if a == b && !is_vowel(*b) && !['l', 's', 'z', 'f'].contains(b) {
+            return stem[..stem.len() - 1].to_string();
+        }
+    }
+
+    if measure(stem) == 1 && ends_cvc(&chars) {
+        return format!("{}e", stem);
+    }
+
+    stem.to_string()
+}
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'e' | 'i' | 'o' | 'u')
+}
+
+/// Number of vowel-consonant sequences in the stem.
+fn measure(stem: &str) -> usize {
+    let mut count = 0;
+    let mut previous_vowel = false;
+
+    for c in stem.chars() {
+        let vowel = is_vowel(c);
+
+        if previous_vowel && !vowel {
+            count += 1;
+        }
+
+        previous_vowel = vowel;
+    }
+
+    count
+}
+
+/// Whether the stem ends with consonant-vowel-consonant, where the last one is
+/// not "w", "x" or "y" (e.g. "hop" but not "show").
+fn ends_cvc(chars: &[char]) -> bool {
+    match chars {
+        [.., c1, v, c2] => {
+            !is_vowel(*c1) && is_vowel(*v) && !is_vowel(*c2) && !['w', 'x', 'y'].contains(c2)
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    #[test_case("went", "go" ; "irregular verb")]
+    #[test_case("children", "child" ; "irregular plural")]
+    #[test_case("studies", "study" ; "ies plural")]
+    #[test_case("boxes", "box" ; "es plural")]
+    #[test_case("cars", "car" ; "s plural")]
+    #[test_case("glass", "glass" ; "double s")]
+    #[test_case("studied", "study" ; "ied past")]
+    #[test_case("walked", "walk" ; "ed past")]
+    #[test_case("hoped", "hope" ; "restored e")]
+    #[test_case("stopped", "stop" ; "doubled consonant")]
+    #[test_case("called", "call" ; "double l")]
+    #[test_case("agreed", "agree" ; "eed past")]
+    #[test_case("eating", "eat" ; "ing")]
+    #[test_case("making", "make" ; "ing restored e")]
+    #[test_case("running", "run" ; "ing doubled consonant")]
+    #[test_case("trying", "try" ; "ing after y")]
+    #[test_case("thing", "thing" ; "ing without vowel")]
+    #[test_case("morning", "morning" ; "ing exception")]
+    #[test_case("opened", "open" ; "long stem")]
+    #[test_case("recycling", "recycle" ; "ing restored le")]
+    #[test_case("curled", "curl" ; "ed after l")]
+    #[test_case("fed", "feed" ; "irregular past")]
+    #[test_case("was", "be" ; "irregular be")]
+    fn test_lemmatize(word: &str, expected: &str) {
+        assert_eq!(lemmatize(word), expected);
+    }
+
+    #[test]
+    fn test_remove_examples() {
+        assert_eq!(
+            remove_examples("to live on (e.g. a salary)"),
+            "to live on ()"
+        );
+        assert_eq!(
+            remove_examples("even (e.g. not even one"),
+            "even (e.g. not even one"
+        );
+    }
+
+    #[test]
+    fn test_context_words() {
+        let words = context_words("to eat (i.e. food). to live on (e.g. a salary). to live off");
+
+        assert_eq!(
+            words,
+            HashSet::from(["eat".to_string(), "food".to_string(), "live".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_context_words_punctuation() {
+        let words = context_words("Japan's \"complicated\" café; well-known");
+
+        assert_eq!(
+            words,
+            HashSet::from([
+                "japan".to_string(),
+                "complicate".to_string(),
+                "caf".to_string(),
+                "well".to_string(),
+                "know".to_string(),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_sense_context_words_english_only() {
+        let word: Word = serde_json::from_str(
+            r#"{
+                "id": "1",
+                "kanji": [],
+                "kana": [],
+                "sense": [{
+                    "partOfSpeech": [], "appliesToKanji": [], "appliesToKana": [],
+                    "related": [], "antonym": [], "field": [], "dialect": [],
+                    "misc": [], "info": [], "languageSource": [],
+                    "gloss": [
+                        { "lang": "eng", "gender": null, "type": null, "text": "to eat" },
+                        { "lang": "ger", "gender": null, "type": null, "text": "essen" }
+                    ]
+                }]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            sense_context_words(&word),
+            vec![HashSet::from(["eat".to_string()])]
+        );
+    }
+}
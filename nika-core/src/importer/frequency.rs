@@ -0,0 +1,165 @@
+use std::collections::HashMap;
+
+use rayon::prelude::*;
+
+use super::matching::Matcher;
+use super::query::Query;
+use crate::errors::ImportError;
+use crate::errors::Result;
+use crate::utils::japanese::JapaneseString;
+
+/// Frequency rank of the words, keyed by word id. The most frequent word has
+/// rank 1.
+pub type FrequencyMap = HashMap<String, u32>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrequencyEntry {
+    pub surface: String,
+    pub reading: Option<String>,
+    pub rank: u32,
+}
+
+impl FrequencyEntry {
+    fn query(&self) -> Query {
+        // the reading of a kana surface form may be written in katakana
+        let reading = self.reading.clone().filter(|_| self.surface.has_kanji());
+
+        Query::new(Some(self.surface.clone()), reading, None)
+    }
+}
+
+/// Parses a frequency list with the surface form, the reading (may be empty)
+/// and the rank of each word, separated by tabs. The header line, if any, is
+/// skipped.
+pub fn parse_tsv(content: &str) -> Result<Vec<FrequencyEntry>> {
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.starts_with('#'))
+        .filter_map(|(i, line)| {
+            let fields = line.split('\t').map(str::trim).collect::<Vec<&str>>();
+
+            let [surface, reading, rank] = fields[..] else {
+                return Some(Err(ImportError::InvalidTsv(
+                    i + 1,
+                    "expected 3 columns".into(),
+                )
+                .into()));
+            };
+
+            match rank.parse::<u32>() {
+                Ok(rank) if !surface.is_empty() => Some(Ok(FrequencyEntry {
+                    surface: surface.to_string(),
+                    reading: Some(reading.to_string()).filter(|r| !r.is_empty()),
+                    rank,
+                })),
+                Ok(_) => Some(Err(ImportError::InvalidTsv(
+                    i + 1,
+                    "empty surface form".into(),
+                )
+                .into())),
+                Err(_) if i == 0 => None,
+                Err(_) => Some(Err(ImportError::InvalidTsv(
+                    i + 1,
+                    format!("invalid rank '{}'", rank),
+                )
+                .into())),
+            }
+        })
+        .collect()
+}
+
+/// Ranks the most likely word of each entry, keeping the best rank of the
+/// words found more than once (e.g. with different surface forms).
+pub fn rank_words(entries: &[FrequencyEntry], matcher: &Matcher) -> FrequencyMap {
+    let ranks = entries
+        .par_iter()
+        .filter_map(|entry| {
+            matcher
+                .rank(&entry.query(), None, None)
+                .first()
+                .map(|candidate| (candidate.word.id.clone(), entry.rank))
+        })
+        .collect::<Vec<(String, u32)>>();
+
+    let mut frequencies = FrequencyMap::new();
+
+    for (id, rank) in ranks {
+        frequencies
+            .entry(id)
+            .and_modify(|current| *current = (*current).min(rank))
+            .or_insert(rank);
+    }
+
+    frequencies
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+
+    use super::*;
+    use crate::errors::NikaError;
+    use crate::importer::context::extract_context_words;
+    use crate::models::jmdict::JMdict;
+
+    fn entry(surface: &str, reading: Option<&str>, rank: u32) -> FrequencyEntry {
+        FrequencyEntry {
+            surface: surface.to_string(),
+            reading: reading.map(String::from),
+            rank,
+        }
+    }
+
+    #[test]
+    fn test_parse_tsv() {
+        let content = "surface\treading\trank\n空\tそら\t10\n\nこし\t\t20\n";
+
+        assert_eq!(
+            parse_tsv(content).unwrap(),
+            vec![entry("空", Some("そら"), 10), entry("こし", None, 20)]
+        );
+    }
+
+    #[test]
+    fn test_parse_tsv_errors() {
+        assert!(matches!(
+            parse_tsv("空\tそら\t10\n紙\tかみ\n").unwrap_err(),
+            NikaError::Import(ImportError::InvalidTsv(2, _))
+        ));
+        assert!(matches!(
+            parse_tsv("空\tそら\t10\n紙\tかみ\tfirst\n").unwrap_err(),
+            NikaError::Import(ImportError::InvalidTsv(2, _))
+        ));
+    }
+
+    #[test]
+    fn test_rank_words() {
+        let fixtures_path = Path::new("tests")
+            .join("fixtures")
+            .join("matcher_words.json");
+
+        let words = fs::read_to_string(fixtures_path).unwrap();
+        let data: JMdict = serde_json::from_str(&words).unwrap();
+        let senses = extract_context_words(&data.words);
+        let matcher = Matcher::new(data.words, senses);
+
+        let entries = vec![
+            entry("空", Some("そら"), 10),
+            entry("空しい", Some("むなしい"), 30),
+            entry("虚しい", Some("むなしい"), 20),
+            entry("こし", Some("コシ"), 40),
+            entry("存在しない", None, 50),
+        ];
+
+        assert_eq!(
+            rank_words(&entries, &matcher),
+            FrequencyMap::from([
+                ("1383470".to_string(), 10),
+                ("1382600".to_string(), 20),
+                ("1288840".to_string(), 40),
+            ])
+        );
+    }
+}
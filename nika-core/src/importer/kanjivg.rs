@@ -0,0 +1,97 @@
+use std::collections::HashMap;
+
+/// SVG paths of the strokes of the kanji, in stroke order, keyed by literal.
+pub type StrokeMap = HashMap<String, Vec<String>>;
+
+/// Size of the KanjiVG drawings, in SVG units.
+pub const KANJIVG_SIZE: f32 = 109.0;
+
+/// Reads the strokes of the KanjiVG content, either the single XML file with
+/// all the kanji or the SVG file of a kanji. The paths are named after the
+/// codepoint of the kanji and their stroke number (e.g. `kvg:065e5-s1`); the
+/// variants of the kanji (e.g. `kvg:065e5-Kaisho-s1`) are left out.
+pub fn parse_strokes(content: &str) -> StrokeMap {
+    let mut strokes = HashMap::<String, Vec<(usize, String)>>::new();
+
+    for tag in content.split("<path").skip(1) {
+        let tag = &tag[..tag.find('>').unwrap_or(tag.len())];
+
+        let (Some(id), Some(path)) = (attribute(tag, "id"), attribute(tag, "d")) else {
+            continue;
+        };
+
+        let Some((code, number)) = id.strip_prefix("kvg:").and_then(|id| id.rsplit_once("-s"))
+        else {
+            continue;
+        };
+
+        let literal = u32::from_str_radix(code, 16).ok().and_then(char::from_u32);
+
+        if let (Some(literal), Ok(number)) = (literal, number.parse::<usize>()) {
+            strokes
+                .entry(literal.to_string())
+                .or_default()
+                .push((number, path.to_string()));
+        }
+    }
+
+    strokes
+        .into_iter()
+        .map(|(literal, mut paths)| {
+            paths.sort_by_key(|(number, _)| *number);
+            (literal, paths.into_iter().map(|(_, path)| path).collect())
+        })
+        .collect()
+}
+
+/// Value of the attribute of the tag, e.g. `d="M..."`.
+fn attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let pattern = format!(" {}=\"", name);
+    let start = tag.find(&pattern)? + pattern.len();
+    let end = tag[start..].find('"')? + start;
+
+    Some(&tag[start..end])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SVG: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" width="109" height="109" viewBox="0 0 109 109">
+<g id="kvg:StrokePaths_04e8c" style="fill:none;stroke:#000000;stroke-width:3;">
+<g id="kvg:04e8c" kvg:element="二" kvg:radical="general">
+	<path id="kvg:04e8c-s2" kvg:type="㇐" d="M14.5,81.5c3.5,1,7.5,1,11.5,0.5"/>
+	<path id="kvg:04e8c-s1" kvg:type="㇐" d="M25.5,28.5c2,0.5,4.5,0.5,7,0.25"/>
+</g>
+</g>
+<g id="kvg:StrokeNumbers_04e8c"><text transform="matrix(1 0 0 1 18.50 25.50)">1</text></g>
+</svg>"#;
+
+    #[test]
+    fn test_parse_strokes() {
+        let strokes = parse_strokes(SVG);
+
+        assert_eq!(
+            strokes["二"],
+            vec![
+                "M25.5,28.5c2,0.5,4.5,0.5,7,0.25",
+                "M14.5,81.5c3.5,1,7.5,1,11.5,0.5"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_strokes_skips_variants() {
+        let content = r#"<kanji id="kvg:kanji_04e00">
+<path id="kvg:04e00-s1" d="M11,54.25c3.75,1,10.5,1,14,0.75"/>
+</kanji>
+<kanji id="kvg:kanji_04e00-Kaisho">
+<path id="kvg:04e00-Kaisho-s1" d="M12,55c4,1,10,1,14,1"/>
+</kanji>"#;
+
+        let strokes = parse_strokes(content);
+
+        assert_eq!(strokes.len(), 1);
+        assert_eq!(strokes["一"], vec!["M11,54.25c3.75,1,10.5,1,14,0.75"]);
+    }
+}
@@ -1,111 +1,247 @@
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::collections::HashSet;
 
-use rayon::prelude::*;
+use serde_derive::Deserialize;
+use serde_derive::Serialize;
 
+use super::context::context_words;
+use super::context::sense_context_words;
+use super::context::SenseMap;
 use super::query::Query;
+use super::resolution::Resolutions;
+use super::scoring::confidences;
+use super::scoring::PartOfSpeechHint;
+use super::scoring::Scorer;
 use crate::models::jmdict::Kana;
 use crate::models::jmdict::Kanji;
 use crate::models::jmdict::Word;
 use crate::utils::japanese::JapaneseString;
 
+/// Outcome of the matching of a query.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "status", content = "ids", rename_all = "camelCase")]
+pub enum MatchStatus {
+    Match(String),
+    Conflict(Vec<String>),
+    Missing,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct QueryResult {
+    /// Position of the query in the imported list.
+    pub index: usize,
+    pub query: Query,
+    #[serde(flatten)]
+    pub status: MatchStatus,
+    /// Confidence of the most likely candidate, zero when missing.
+    pub confidence: f64,
+}
+
+/// Results of the matching of a list of queries, in the same order.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct MatchResult {
+    pub results: Vec<QueryResult>,
+}
+
+impl MatchResult {
+    pub fn matches(&self) -> impl Iterator<Item = &QueryResult> {
+        self.results
+            .iter()
+            .filter(|r| matches!(r.status, MatchStatus::Match(_)))
+    }
+
+    pub fn conflicts(&self) -> impl Iterator<Item = &QueryResult> {
+        self.results
+            .iter()
+            .filter(|r| matches!(r.status, MatchStatus::Conflict(_)))
+    }
+
+    pub fn missing(&self) -> impl Iterator<Item = &QueryResult> {
+        self.results
+            .iter()
+            .filter(|r| matches!(r.status, MatchStatus::Missing))
+    }
+
+    /// Returns the matched words together with the ones chosen for the
+    /// conflicts, keeping the order of the imported list.
+    pub fn merge(&self, resolutions: &Resolutions) -> Vec<String> {
+        let mut seen = HashSet::new();
+
+        self.results
+            .iter()
+            .flat_map(|result| match &result.status {
+                MatchStatus::Match(id) => vec![id.clone()],
+                MatchStatus::Conflict(_) => resolutions
+                    .get(&result.query)
+                    .map(|ids| ids.to_vec())
+                    .unwrap_or_default(),
+                MatchStatus::Missing => vec![],
+            })
+            .filter(|id| seen.insert(id.clone()))
+            .collect()
+    }
+}
+
+/// Confidence above which the most likely candidate of a query is accepted.
+pub const DEFAULT_CONFIDENCE: f64 = 0.75;
+
+/// Candidate word of a query with its score and its share of the evidence of
+/// the candidates.
+#[derive(Debug, Clone)]
+pub struct Candidate<'a> {
+    pub word: &'a Word,
+    pub score: f64,
+    pub confidence: f64,
+}
+
 pub struct Matcher {
     words: Vec<Word>,
-    senses_map: HashMap<String, HashSet<String>>,
+    senses_map: SenseMap,
+    scorer: Scorer,
+    /// Positions of the words by kanji text, in dictionary order.
+    kanji_index: HashMap<String, Vec<usize>>,
+    /// Positions of the words by kana text, in dictionary order.
+    kana_index: HashMap<String, Vec<usize>>,
+    id_index: HashMap<String, usize>,
 }
 
 impl Matcher {
-    pub fn new(words: Vec<Word>, senses_map: HashMap<String, HashSet<String>>) -> Self {
-        Self { words, senses_map }
+    pub fn new(mut words: Vec<Word>, senses_map: SenseMap) -> Self {
+        // the candidates are returned in dictionary order, the JMdict ids
+        // being numbers (e.g. 9999999 before 10000000)
+        words.sort_by_cached_key(|word| {
+            (word.id.parse::<u64>().unwrap_or(u64::MAX), word.id.clone())
+        });
+
+        let mut kanji_index: HashMap<String, Vec<usize>> = HashMap::new();
+        let mut kana_index: HashMap<String, Vec<usize>> = HashMap::new();
+        let mut id_index = HashMap::with_capacity(words.len());
+
+        for (position, word) in words.iter().enumerate() {
+            for kanji in &word.kanji {
+                index_word(&mut kanji_index, &kanji.text.normalize(), position);
+            }
+
+            for kana in &word.kana {
+                index_word(&mut kana_index, &kana.text.normalize(), position);
+            }
+
+            id_index.insert(word.id.clone(), position);
+        }
+
+        Self {
+            words,
+            scorer: Scorer::new(&senses_map),
+            senses_map,
+            kanji_index,
+            kana_index,
+            id_index,
+        }
     }
 
+    /// Returns the words matching the forms of the query, regardless of the
+    /// kana script, the width and the long vowels, in dictionary order.
     pub fn find(
         &self,
         query: &Query,
         common: Option<bool>,
         first_only: Option<bool>,
     ) -> Vec<&Word> {
-        let results: Vec<&Word> = self
-            .words
-            .par_iter()
-            .filter(|word| filter_word(word, query, common, first_only))
+        let query = Query::new(
+            query.kanji.as_deref().map(str::normalize),
+            query.kana.as_deref().map(str::normalize),
+            None,
+        );
+
+        self.lookup(&query)
+            .iter()
+            .map(|&position| &self.words[position])
+            .filter(|word| filter_word(word, &query, common, first_only))
+            .collect()
+    }
+
+    /// Returns the words matching the forms of the query, from the most
+    /// likely one according to the query meaning.
+    pub fn rank(
+        &self,
+        query: &Query,
+        common: Option<bool>,
+        first_only: Option<bool>,
+    ) -> Vec<Candidate<'_>> {
+        let mut candidates = self.score(query, self.find(query, common, first_only));
+
+        // the sort is stable, so the ties keep the dictionary order
+        candidates.sort_by(|a, b| b.score.total_cmp(&a.score));
+        candidates
+    }
+
+    /// Returns the given words scored against the query, in the same order.
+    pub fn candidates(&self, query: &Query, ids: &[String]) -> Vec<Candidate<'_>> {
+        let words = ids
+            .iter()
+            .filter_map(|id| self.id_index.get(id))
+            .map(|&position| &self.words[position])
             .collect();
 
-        if results.len() > 1 {
-            let mut debug_info = String::new();
-
-            if let Some(meaning) = &query.meaning {
-                let context_words = meaning.split_whitespace().collect::<HashSet<&str>>();
-
-                debug_info += &format!(
-                    "\n\nLooking for {} ({})\n",
-                    query.kanji.clone().unwrap(),
-                    context_words
-                        .iter()
-                        .map(|w| w.to_owned())
-                        .collect::<Vec<&str>>()
-                        .join(", ")
-                );
-
-                let mut counts = HashMap::<&Word, usize>::new();
-
-                for word in &results {
-                    let senses = self
-                        .senses_map
-                        .get(&word.id)
-                        .unwrap_or_else(|| {
-                            panic!(
-                                "Senses not found for word {}",
-                                word.kanji
-                                    .first()
-                                    .map_or(word.kana.first().unwrap().text.as_str(), |k| k
-                                        .text
-                                        .as_str())
-                            )
-                        })
-                        .iter()
-                        .map(|s| s.as_str())
-                        .collect();
-
-                    let n_overlaps = context_words.intersection(&senses).count();
-
-                    debug_info += &format!(
-                        "[{}]\t({}, {})\n",
-                        n_overlaps,
-                        word.kanji
-                            .iter()
-                            .map(|k| k.text.clone())
-                            .collect::<Vec<String>>()
-                            .join(", "),
-                        senses
-                            .iter()
-                            .map(|s| s.to_owned())
-                            .collect::<Vec<&str>>()
-                            .join(", "),
-                    );
-
-                    counts.insert(word, n_overlaps);
-                }
-
-                debug_info += "\n";
-
-                let max_count = *counts.values().max().unwrap_or(&0);
-
-                let r = results
-                    .iter()
-                    .filter(|w| *counts.get(*w).unwrap_or(&0) == max_count)
-                    .copied()
-                    .collect::<Vec<&Word>>();
-
-                if r.len() > 1 {
-                    println!("{}", debug_info);
-                }
-
-                return r;
-            }
+        self.score(query, words)
+    }
+
+    fn score<'a>(&self, query: &Query, words: Vec<&'a Word>) -> Vec<Candidate<'a>> {
+        let meaning = query.meaning.as_deref().unwrap_or_default();
+        let context_words = context_words(meaning);
+        let hint = PartOfSpeechHint::from_meaning(meaning);
+
+        let evidence = words
+            .iter()
+            .map(|word| {
+                let senses = self.senses(word);
+                self.scorer.evidence(&context_words, hint, word, &senses)
+            })
+            .collect::<Vec<f64>>();
+
+        words
+            .into_iter()
+            .zip(confidences(&evidence))
+            .zip(evidence)
+            .map(|((word, confidence), evidence)| Candidate {
+                word,
+                score: self.scorer.score(evidence, word),
+                confidence,
+            })
+            .collect()
+    }
+
+    /// Positions of the words that may match the query, following the
+    /// forms checked by `filter_word`.
+    fn lookup(&self, query: &Query) -> &[usize] {
+        let (index, text) = match (&query.kanji, &query.kana) {
+            (Some(kanji), Some(kana)) if kanji != kana => (&self.kanji_index, kanji),
+            (Some(text), _) | (None, Some(text)) if text.has_kanji() => (&self.kanji_index, text),
+            (Some(text), _) | (None, Some(text)) => (&self.kana_index, text),
+            (None, None) => return &[],
+        };
+
+        index
+            .get(text)
+            .map_or(&[], |positions| positions.as_slice())
+    }
+
+    fn senses(&self, word: &Word) -> Cow<'_, [HashSet<String>]> {
+        match self.senses_map.get(&word.id) {
+            Some(senses) => Cow::Borrowed(senses),
+            // the senses file may be older than the dictionary
+            None => Cow::Owned(sense_context_words(word)),
         }
+    }
+}
 
-        results
+fn index_word(index: &mut HashMap<String, Vec<usize>>, text: &str, position: usize) {
+    let positions = index.entry(text.to_string()).or_default();
+
+    // a word may repeat the same text (e.g. with different tags)
+    if positions.last() != Some(&position) {
+        positions.push(position);
     }
 }
 
@@ -113,11 +249,12 @@ trait KanjiOrKana {
     fn text(&self) -> &String;
     fn common(&self) -> &bool;
 
+    /// Whether the element matches the normalized text.
     fn matches(&self, text: &str, common: &Option<bool>) -> bool {
         if let Some(common) = common {
-            self.text() == text && self.common() == common
+            self.text().normalize() == text && self.common() == common
         } else {
-            self.text() == text
+            self.text().normalize() == text
         }
     }
 }
@@ -158,7 +295,7 @@ fn filter_word(word: &Word, query: &Query, common: Option<bool>, first_only: Opt
     }
 }
 
-fn filter<T: KanjiOrKana + Sync>(
+fn filter<T: KanjiOrKana>(
     elements: &[T],
     text: &str,
     common: Option<bool>,
@@ -167,8 +304,8 @@ fn filter<T: KanjiOrKana + Sync>(
     if let Some((first, rest)) = elements.split_first() {
         return match first_only {
             Some(true) => first.matches(text, &common),
-            Some(false) => rest.par_iter().any(|k| k.matches(text, &common)),
-            None => elements.par_iter().any(|k| k.matches(text, &common)),
+            Some(false) => rest.iter().any(|k| k.matches(text, &common)),
+            None => elements.iter().any(|k| k.matches(text, &common)),
         };
     }
 
@@ -195,29 +332,35 @@ fn filter_kanji_kana(
     common: Option<bool>,
     first_only: Option<bool>,
 ) -> bool {
-    filter(&word.kanji, kanji, common, first_only) && word.kana.par_iter().any(|k| k.text == *kana)
+    filter(&word.kanji, kanji, common, first_only)
+        && word.kana.iter().any(|k| k.text.normalize() == kana)
 }
 
-// TODO: test order of the entries after the import.
-
 #[cfg(test)]
 mod tests {
     use std::fs;
-    use std::path::PathBuf;
+    use std::path::Path;
 
     use test_case::test_case;
 
     use super::*;
-    use crate::repositories::dictionary_repository::WordMap;
+    use crate::importer::context::extract_context_words;
+    use crate::models::jmdict::JMdict;
 
     fn setup() -> Matcher {
-        let dict = bincode::deserialize::<WordMap>(
-            &fs::read(PathBuf::from("/home/giuseppe/.local/share/nika/jmdict-words.bin").as_path())
-                .expect("cannot read words"),
-        )
-        .expect("cannot load words");
+        let fixtures_path = Path::new("tests")
+            .join("fixtures")
+            .join("matcher_words.json");
+
+        let words = fs::read_to_string(fixtures_path).unwrap();
+        let data: JMdict = serde_json::from_str(&words).unwrap();
+        let senses = extract_context_words(&data.words);
 
-        Matcher::new(dict.values().cloned().collect(), HashMap::new())
+        Matcher::new(data.words, senses)
+    }
+
+    fn ids(words: Vec<&Word>) -> Vec<&str> {
+        words.iter().map(|w| w.id.as_str()).collect()
     }
 
     #[test]
@@ -227,7 +370,7 @@ mod tests {
         let query = Query::new(Some("空しい".to_owned()), Some("むなしい".to_owned()), None);
 
         let results = matcher.find(&query, None, None);
-        assert_eq!(results.len(), 1);
+        assert_eq!(ids(results), vec!["1382600"]);
     }
 
     #[test_case("空", 3)]
@@ -259,7 +402,7 @@ mod tests {
         let query = Query::new(Some("髪".to_owned()), Some("かみ".to_owned()), None);
         let results = matcher.find(&query, None, None);
 
-        assert_eq!(results.len(), 1);
+        assert_eq!(ids(results), vec!["1258650"]);
     }
 
     #[test_case("髪髪髪", "かみ", 0)]
@@ -273,14 +416,104 @@ mod tests {
         assert_eq!(results.len(), count);
     }
 
-    #[test_case(None, 9)]
-    #[test_case(Some("recycle for paper use".to_owned()), 1)]
-    fn test_match_meaning(meaning: Option<String>, count: usize) {
+    #[test_case(None, "1288840", false ; "common word")]
+    #[test_case(Some("paper for recycling".to_owned()), "1269890", true ; "meaning")]
+    fn test_rank_meaning(meaning: Option<String>, id: &str, accepted: bool) {
         let matcher = setup();
 
         let query = Query::new(Some("こし".to_owned()), Some("こし".to_owned()), meaning);
-        let results = matcher.find(&query, None, Some(true));
+        let candidates = matcher.rank(&query, None, Some(true));
 
-        assert_eq!(results.len(), count);
+        assert_eq!(candidates.len(), 4);
+        assert_eq!(candidates[0].word.id, id);
+        assert_eq!(candidates[0].confidence >= DEFAULT_CONFIDENCE, accepted);
+    }
+
+    #[test_case(None, "1222100" ; "common word")]
+    #[test_case(Some("(v)".to_owned()), "1222220" ; "verb")]
+    fn test_rank_part_of_speech(meaning: Option<String>, id: &str) {
+        let matcher = setup();
+
+        let query = Query::new(None, Some("きそく".to_owned()), meaning);
+        let candidates = matcher.rank(&query, None, None);
+
+        assert_eq!(candidates[0].word.id, id);
+    }
+
+    #[test]
+    fn test_results_in_dictionary_order() {
+        let matcher = setup();
+
+        let query = Query::new(None, Some("きそく".to_owned()), None);
+
+        let results = matcher.find(&query, None, None);
+        assert_eq!(
+            ids(results),
+            vec!["1222100", "1222220", "1222240", "1222250"]
+        );
+    }
+
+    #[test]
+    fn test_candidates_overlaps() {
+        let matcher = setup();
+
+        let query = Query::new(Some("空".to_owned()), None, Some("the sky".to_owned()));
+        let ids = vec!["1383470".to_string(), "1245280".to_string()];
+
+        let candidates = matcher
+            .candidates(&query, &ids)
+            .into_iter()
+            .map(|candidate| (candidate.word.id.as_str(), candidate.score))
+            .collect::<Vec<(&str, f64)>>();
+
+        assert_eq!(candidates[0].0, "1383470");
+        assert_eq!(candidates[1].0, "1245280");
+        assert!(candidates[0].1 > candidates[1].1);
+    }
+
+    #[test]
+    fn test_match_meaning_without_senses() {
+        let fixtures_path = Path::new("tests")
+            .join("fixtures")
+            .join("matcher_words.json");
+
+        let words = fs::read_to_string(fixtures_path).unwrap();
+        let data: JMdict = serde_json::from_str(&words).unwrap();
+        let matcher = Matcher::new(data.words, SenseMap::new());
+
+        let query = Query::new(
+            Some("こし".to_owned()),
+            Some("こし".to_owned()),
+            Some("paper for recycling".to_owned()),
+        );
+
+        let candidates = matcher.rank(&query, None, Some(true));
+
+        assert_eq!(candidates[0].word.id, "1269890");
+    }
+
+    #[test_case("むなしい" ; "hiragana")]
+    #[test_case("ムナシイ" ; "katakana")]
+    #[test_case("ﾑﾅｼｲ" ; "half width")]
+    fn test_match_normalized_kana(kana: &str) {
+        let matcher = setup();
+
+        let query = Query::new(Some("空しい".to_owned()), Some(kana.to_owned()), None);
+        assert_eq!(ids(matcher.find(&query, None, None)), vec!["1382600"]);
+
+        let query = Query::new(None, Some(kana.to_owned()), None);
+        assert_eq!(ids(matcher.find(&query, None, None)), vec!["1382600"]);
+    }
+
+    #[test]
+    fn test_match_missing_text() {
+        let matcher = setup();
+
+        let query = Query::new(Some("存在しない".to_owned()), None, None);
+
+        assert!(matcher.find(&query, None, None).is_empty());
+        assert!(matcher
+            .find(&Query::new(None, None, None), None, None)
+            .is_empty());
     }
 }
@@ -1,2 +1,9 @@
+pub mod accent;
+pub mod context;
+pub mod frequency;
+pub mod kanjivg;
 pub mod matching;
 pub mod query;
+pub mod resolution;
+pub mod scoring;
+pub mod tatoeba;
@@ -1,8 +1,12 @@
 use std::fmt::Display;
+use std::result;
 
 use serde_derive::Deserialize;
 use serde_derive::Serialize;
 
+use crate::errors::ImportError;
+use crate::errors::Result;
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Query {
     pub kanji: Option<String>,
@@ -25,3 +29,142 @@ impl Display for Query {
         write!(f, "kanji: {:?}, kana: {:?}", self.kanji, self.kana)
     }
 }
+
+/// Parses the queries from a JSON array of `{ kanji, kana, meaning }` objects.
+pub fn parse_json(content: &str) -> Result<Vec<Query>> {
+    Ok(serde_json::from_str::<Vec<Query>>(content)?)
+}
+
+/// Parses the queries from a CSV with a header naming the `kanji`, `kana` and
+/// `meaning` columns, in any order.
+pub fn parse_csv(content: &str) -> Result<Vec<Query>> {
+    let mut lines = content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty());
+
+    let Some((_, header)) = lines.next() else {
+        return Ok(vec![]);
+    };
+
+    let header = split_csv_line(header).map_err(|e| ImportError::InvalidCsv(1, e))?;
+    let column = |name: &str| {
+        header
+            .iter()
+            .position(|h| h.trim().eq_ignore_ascii_case(name))
+    };
+
+    let (kanji, kana, meaning) = (column("kanji"), column("kana"), column("meaning"));
+
+    if kanji.is_none() && kana.is_none() {
+        return Err(ImportError::InvalidCsv(1, "missing kanji or kana column".into()).into());
+    }
+
+    lines
+        .map(|(i, line)| {
+            let fields = split_csv_line(line).map_err(|e| ImportError::InvalidCsv(i + 1, e))?;
+
+            let field = |index: Option<usize>| {
+                index
+                    .and_then(|index| fields.get(index))
+                    .map(|f| f.trim())
+                    .filter(|f| !f.is_empty())
+                    .map(String::from)
+            };
+
+            Ok(Query::new(field(kanji), field(kana), field(meaning)))
+        })
+        .collect()
+}
+
+/// Splits a CSV line, handling the quoted fields and the escaped quotes.
+fn split_csv_line(line: &str) -> result::Result<Vec<String>, String> {
+    let mut fields = vec![];
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (c, quoted) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            ('"', true) => quoted = false,
+            ('"', false) if field.trim().is_empty() => {
+                field.clear();
+                quoted = true;
+            }
+            (',', false) => fields.push(std::mem::take(&mut field)),
+            (c, _) => field.push(c),
+        }
+    }
+
+    if quoted {
+        return Err("unterminated quoted field".into());
+    }
+
+    fields.push(field);
+
+    Ok(fields)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::NikaError;
+
+    fn query(kanji: Option<&str>, kana: Option<&str>, meaning: Option<&str>) -> Query {
+        Query::new(
+            kanji.map(String::from),
+            kana.map(String::from),
+            meaning.map(String::from),
+        )
+    }
+
+    #[test]
+    fn test_parse_csv() {
+        let content = "kana,kanji,meaning\nたべる,食べる,to eat\nとして,,\"as, for\"\n\n";
+
+        let queries = parse_csv(content).unwrap();
+
+        assert_eq!(
+            queries,
+            vec![
+                query(Some("食べる"), Some("たべる"), Some("to eat")),
+                query(None, Some("として"), Some("as, for")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_csv_escaped_quotes() {
+        let content = "kanji,meaning\n空,\"the \"\"sky\"\"\"";
+
+        let queries = parse_csv(content).unwrap();
+
+        assert_eq!(queries, vec![query(Some("空"), None, Some("the \"sky\""))]);
+    }
+
+    #[test]
+    fn test_parse_csv_errors() {
+        assert!(matches!(
+            parse_csv("meaning\nto eat").unwrap_err(),
+            NikaError::Import(ImportError::InvalidCsv(1, _))
+        ));
+        assert!(matches!(
+            parse_csv("kanji\n\"空").unwrap_err(),
+            NikaError::Import(ImportError::InvalidCsv(2, _))
+        ));
+        assert!(parse_csv("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_parse_json() {
+        let content = r#"[{"kanji": "空", "kana": "そら", "meaning": null}]"#;
+
+        let queries = parse_json(content).unwrap();
+
+        assert_eq!(queries, vec![query(Some("空"), Some("そら"), None)]);
+    }
+}
@@ -0,0 +1,147 @@
+use std::fs;
+use std::path::Path;
+
+use serde_derive::Deserialize;
+use serde_derive::Serialize;
+
+use super::query::Query;
+use crate::errors::Result;
+
+/// Words chosen for an ambiguous query, none when the query is discarded.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Resolution {
+    pub query: Query,
+    pub ids: Vec<String>,
+}
+
+/// Decisions taken on the conflicts of an import, saved to be reused when the
+/// same list is imported again.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct Resolutions {
+    resolutions: Vec<Resolution>,
+}
+
+impl Resolutions {
+    pub fn load<P: AsRef<Path>>(filepath: &P) -> Result<Resolutions> {
+        if !filepath.as_ref().exists() {
+            return Ok(Resolutions::default());
+        }
+
+        let file = fs::read_to_string(filepath)?;
+        let resolutions = serde_json::from_str::<Resolutions>(&file)?;
+
+        Ok(resolutions)
+    }
+
+    pub fn save<P: AsRef<Path>>(filepath: &P, resolutions: &Resolutions) -> Result<()> {
+        let content = serde_json::to_string_pretty(resolutions)?;
+        fs::write(filepath, content)?;
+
+        Ok(())
+    }
+
+    pub fn get(&self, query: &Query) -> Option<&[String]> {
+        self.resolutions
+            .iter()
+            .find(|r| r.query == *query)
+            .map(|r| r.ids.as_slice())
+    }
+
+    /// Sets the words chosen for the query, replacing the previous decision.
+    pub fn set(&mut self, query: &Query, ids: Vec<String>) {
+        match self.resolutions.iter_mut().find(|r| r.query == *query) {
+            Some(resolution) => resolution.ids = ids,
+            None => self.resolutions.push(Resolution {
+                query: query.clone(),
+                ids,
+            }),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.resolutions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.resolutions.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::tempdir;
+
+    use super::*;
+    use crate::importer::matching::MatchResult;
+    use crate::importer::matching::MatchStatus;
+    use crate::importer::matching::QueryResult;
+
+    fn query(kanji: &str) -> Query {
+        Query::new(Some(kanji.to_string()), None, None)
+    }
+
+    #[test]
+    fn test_set_replaces_decision() {
+        let mut resolutions = Resolutions::default();
+
+        resolutions.set(&query("空"), vec!["1".into()]);
+        resolutions.set(&query("空"), vec![]);
+
+        assert_eq!(resolutions.len(), 1);
+        assert_eq!(resolutions.get(&query("空")), Some([].as_slice()));
+        assert_eq!(resolutions.get(&query("髪")), None);
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let filepath = tempdir().unwrap().into_path().join("resolutions.json");
+
+        assert!(Resolutions::load(&filepath).unwrap().is_empty());
+
+        let mut resolutions = Resolutions::default();
+        resolutions.set(&query("空"), vec!["1".into(), "2".into()]);
+        Resolutions::save(&filepath, &resolutions).unwrap();
+
+        assert_eq!(Resolutions::load(&filepath).unwrap(), resolutions);
+    }
+
+    #[test]
+    fn test_merge_keeps_order() {
+        let result = |index: usize, kanji: &str, status: MatchStatus| QueryResult {
+            index,
+            query: query(kanji),
+            status,
+            confidence: 0.0,
+        };
+
+        let result = MatchResult {
+            results: vec![
+                result(0, "食べる", MatchStatus::Match("a".into())),
+                result(
+                    1,
+                    "空",
+                    MatchStatus::Conflict(vec!["b1".into(), "b2".into(), "b3".into()]),
+                ),
+                result(2, "日本", MatchStatus::Match("c".into())),
+                result(3, "自由", MatchStatus::Missing),
+                result(
+                    4,
+                    "髪",
+                    MatchStatus::Conflict(vec!["e1".into(), "e2".into()]),
+                ),
+                result(
+                    5,
+                    "紙",
+                    MatchStatus::Conflict(vec!["f1".into(), "f2".into()]),
+                ),
+                result(6, "日本", MatchStatus::Match("c".into())),
+            ],
+        };
+
+        let mut resolutions = Resolutions::default();
+        resolutions.set(&query("空"), vec!["b3".into(), "b1".into()]);
+        resolutions.set(&query("髪"), vec![]);
+
+        assert_eq!(result.merge(&resolutions), vec!["a", "b3", "b1", "c"]);
+    }
+}
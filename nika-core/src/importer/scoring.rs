@@ -0,0 +1,246 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use super::context::SenseMap;
+use crate::models::jmdict::Word;
+
+/// Score given to every candidate, so that a clear winner among several
+/// candidates is never fully certain. It is shared among the candidates for
+/// their confidence, which does not depend on the number of candidates.
+const BASE_SCORE: f64 = 0.05;
+const COMMON_SCORE: f64 = 0.1;
+const PART_OF_SPEECH_SCORE: f64 = 0.2;
+
+/// Part of speech suggested by the meaning of a query, e.g. "to eat" or
+/// "rule (n)".
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PartOfSpeechHint {
+    Noun,
+    Verb,
+    Adjective,
+    Adverb,
+}
+
+impl PartOfSpeechHint {
+    pub fn from_meaning(meaning: &str) -> Option<Self> {
+        let meaning = meaning.trim().to_lowercase();
+
+        let hint = meaning
+            .split('(')
+            .skip(1)
+            .filter_map(|rest| rest.split_once(')'))
+            .find_map(|(hint, _)| match hint.trim() {
+                "n" | "noun" => Some(Self::Noun),
+                "v" | "verb" => Some(Self::Verb),
+                "adj" | "adjective" => Some(Self::Adjective),
+                "adv" | "adverb" => Some(Self::Adverb),
+                _ => None,
+            });
+
+        hint.or_else(|| meaning.starts_with("to ").then_some(Self::Verb))
+    }
+
+    /// Whether the JMdict part of speech tag (e.g. "v5r" or "adj-i") is of
+    /// this kind.
+    pub fn matches(&self, tag: &str) -> bool {
+        match self {
+            Self::Noun => tag == "n" || tag.starts_with("n-"),
+            Self::Verb => tag.starts_with('v'),
+            Self::Adjective => tag.starts_with("adj"),
+            Self::Adverb => tag.starts_with("adv"),
+        }
+    }
+}
+
+/// Scores the candidates of a query by the context words shared with their
+/// senses, weighted by the inverse document frequency of each word across the
+/// dictionary and by the order of the senses.
+pub struct Scorer {
+    idf: HashMap<String, f64>,
+    /// Weight of the words not found in the dictionary.
+    max_idf: f64,
+}
+
+impl Scorer {
+    pub fn new(senses_map: &SenseMap) -> Self {
+        let mut frequencies: HashMap<&str, usize> = HashMap::new();
+
+        for senses in senses_map.values() {
+            let words = senses
+                .iter()
+                .flatten()
+                .map(String::as_str)
+                .collect::<HashSet<&str>>();
+
+            for word in words {
+                *frequencies.entry(word).or_default() += 1;
+            }
+        }
+
+        let count = senses_map.len() as f64;
+        let idf = |frequency: usize| ((count + 1.0) / (frequency as f64 + 1.0)).ln() + 1.0;
+
+        Self {
+            idf: frequencies
+                .into_iter()
+                .map(|(word, frequency)| (word.to_string(), idf(frequency)))
+                .collect(),
+            max_idf: idf(0),
+        }
+    }
+
+    fn idf(&self, word: &str) -> f64 {
+        self.idf.get(word).copied().unwrap_or(self.max_idf)
+    }
+
+    /// Share of the query context words found in the senses, between 0 and 1.
+    /// Each word counts for its first sense, weighted 1 for the first sense,
+    /// 1/2 for the second and so on.
+    pub fn relevance(&self, context_words: &HashSet<String>, senses: &[HashSet<String>]) -> f64 {
+        let total = context_words.iter().map(|w| self.idf(w)).sum::<f64>();
+
+        if total == 0.0 {
+            return 0.0;
+        }
+
+        let found = context_words
+            .iter()
+            .filter_map(|w| {
+                senses
+                    .iter()
+                    .position(|sense| sense.contains(w))
+                    .map(|position| self.idf(w) * sense_weight(position))
+            })
+            .sum::<f64>();
+
+        found / total
+    }
+
+    /// Relevance of the senses to the query, and agreement of their part of
+    /// speech with the hint. The common words get no evidence.
+    pub fn evidence(
+        &self,
+        context_words: &HashSet<String>,
+        hint: Option<PartOfSpeechHint>,
+        word: &Word,
+        senses: &[HashSet<String>],
+    ) -> f64 {
+        let mut evidence = self.relevance(context_words, senses);
+
+        if let Some(hint) = hint {
+            let position = word
+                .sense
+                .iter()
+                .position(|sense| sense.part_of_speech.iter().any(|tag| hint.matches(tag)));
+
+            if let Some(position) = position {
+                evidence += PART_OF_SPEECH_SCORE * sense_weight(position);
+            }
+        }
+
+        evidence
+    }
+
+    /// Score ranking the candidates, favoring the common words.
+    pub fn score(&self, evidence: f64, word: &Word) -> f64 {
+        let mut score = BASE_SCORE + evidence;
+
+        if word.is_common() {
+            score += COMMON_SCORE;
+        }
+
+        score
+    }
+}
+
+fn sense_weight(position: usize) -> f64 {
+    1.0 / (position + 1) as f64
+}
+
+/// Share of the total evidence of each candidate, smoothed by the base score.
+/// The constant terms of the scores are left out, so that many common
+/// candidates don't dilute the confidence of the only relevant one.
+pub fn confidences(evidence: &[f64]) -> Vec<f64> {
+    let base = BASE_SCORE / evidence.len() as f64;
+    let total = evidence.iter().sum::<f64>() + BASE_SCORE;
+
+    evidence.iter().map(|e| (e + base) / total).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    fn words(words: &[&str]) -> HashSet<String> {
+        words.iter().map(|w| w.to_string()).collect()
+    }
+
+    fn senses_map() -> SenseMap {
+        SenseMap::from([
+            ("1".to_string(), vec![words(&["sky", "heaven"])]),
+            ("2".to_string(), vec![words(&["empty"]), words(&["sky"])]),
+            ("3".to_string(), vec![words(&["empty", "air"])]),
+        ])
+    }
+
+    #[test_case("to eat", Some(PartOfSpeechHint::Verb) ; "infinitive")]
+    #[test_case("rule (n)", Some(PartOfSpeechHint::Noun) ; "noun tag")]
+    #[test_case("(adjective) empty", Some(PartOfSpeechHint::Adjective) ; "adjective tag")]
+    #[test_case("to and fro (adv)", Some(PartOfSpeechHint::Adverb) ; "tag before infinitive")]
+    #[test_case("hair (on the head)", None ; "no hint")]
+    fn test_part_of_speech_hint(meaning: &str, expected: Option<PartOfSpeechHint>) {
+        assert_eq!(PartOfSpeechHint::from_meaning(meaning), expected);
+    }
+
+    #[test_case(PartOfSpeechHint::Noun, "n-adv", true)]
+    #[test_case(PartOfSpeechHint::Noun, "num", false)]
+    #[test_case(PartOfSpeechHint::Verb, "v5r", true)]
+    #[test_case(PartOfSpeechHint::Adjective, "adj-i", true)]
+    #[test_case(PartOfSpeechHint::Adverb, "adj-na", false)]
+    fn test_part_of_speech_matches(hint: PartOfSpeechHint, tag: &str, expected: bool) {
+        assert_eq!(hint.matches(tag), expected);
+    }
+
+    #[test]
+    fn test_relevance_sense_order() {
+        let senses_map = senses_map();
+        let scorer = Scorer::new(&senses_map);
+
+        let first = scorer.relevance(&words(&["sky"]), &senses_map["1"]);
+        let second = scorer.relevance(&words(&["sky"]), &senses_map["2"]);
+
+        assert_eq!(first, 1.0);
+        assert_eq!(second, 0.5);
+    }
+
+    #[test]
+    fn test_relevance_idf() {
+        let senses_map = senses_map();
+        let scorer = Scorer::new(&senses_map);
+
+        // "air" is rarer than "empty" in the dictionary
+        let context_words = words(&["empty", "air"]);
+        let air = scorer.relevance(&context_words, &[words(&["air"])]);
+        let empty = scorer.relevance(&context_words, &[words(&["empty"])]);
+
+        assert!(air > empty);
+        assert_eq!(scorer.relevance(&HashSet::new(), &senses_map["1"]), 0.0);
+    }
+
+    #[test]
+    fn test_confidences() {
+        assert_eq!(confidences(&[3.95, 0.0]), vec![0.99375, 0.00625]);
+        assert_eq!(confidences(&[0.0, 0.0]), vec![0.5, 0.5]);
+        assert!(confidences(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_confidences_many_candidates() {
+        let mut evidence = vec![0.0; 20];
+        evidence[0] = 1.0;
+
+        assert!(confidences(&evidence)[0] > 0.95);
+    }
+}
@@ -0,0 +1,325 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::io::BufRead;
+
+use serde_derive::Deserialize;
+use serde_derive::Serialize;
+
+use crate::errors::ImportError;
+use crate::errors::Result;
+use crate::models::jmdict::Word;
+
+/// Most examples kept for each word.
+pub const MAX_EXAMPLES: usize = 10;
+
+/// Example sentences of the words, keyed by word id.
+pub type ExampleMap = HashMap<String, Vec<Example>>;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub japanese: String,
+    pub translation: Option<String>,
+}
+
+/// Word of a sentence in the Tatoeba index, e.g. `食べる(たべる)[01]{食べた}~`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Headword {
+    pub text: String,
+    pub reading: Option<String>,
+    pub sense: Option<usize>,
+    /// Form of the word in the sentence, when inflected.
+    pub form: Option<String>,
+    /// Whether the sentence is a good example of the word.
+    pub checked: bool,
+}
+
+/// Japanese sentence of the Tatoeba index, with the id of its English
+/// translation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexEntry {
+    pub sentence_id: u64,
+    pub translation_id: Option<u64>,
+    pub headwords: Vec<Headword>,
+}
+
+fn parse_headword(token: &str) -> Option<Headword> {
+    let end = token.find(['(', '[', '{', '~']).unwrap_or(token.len());
+    let (text, mut rest) = token.split_at(end);
+
+    let mut headword = Headword {
+        text: text.to_string(),
+        reading: None,
+        sense: None,
+        form: None,
+        checked: false,
+    };
+
+    while let Some(c) = rest.chars().next() {
+        let close = match c {
+            '(' => ')',
+            '[' => ']',
+            '{' => '}',
+            '~' => {
+                headword.checked = true;
+                rest = &rest[1..];
+                continue;
+            }
+            _ => return None,
+        };
+
+        let (value, after) = rest[1..].split_once(close)?;
+
+        match c {
+            '(' => headword.reading = Some(value.to_string()),
+            '[' => headword.sense = value.parse().ok(),
+            _ => headword.form = Some(value.to_string()),
+        }
+
+        rest = after;
+    }
+
+    (!headword.text.is_empty()).then_some(headword)
+}
+
+/// Parses the `jpn_indices.csv` export, with the id of the Japanese sentence,
+/// the id of its translation (-1 if none) and the headwords of the sentence
+/// separated by tabs.
+pub fn parse_indices(content: &str) -> Result<Vec<IndexEntry>> {
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            let invalid = |message: &str| ImportError::InvalidTsv(i + 1, message.into());
+
+            let [sentence_id, translation_id, headwords] =
+                line.splitn(3, '\t').collect::<Vec<&str>>()[..]
+            else {
+                return Err(invalid("expected 3 columns").into());
+            };
+
+            let sentence_id = sentence_id
+                .trim()
+                .parse::<u64>()
+                .map_err(|_| invalid("invalid sentence id"))?;
+            let translation_id = translation_id
+                .trim()
+                .parse::<i64>()
+                .map_err(|_| invalid("invalid translation id"))?;
+
+            Ok(IndexEntry {
+                sentence_id,
+                translation_id: u64::try_from(translation_id).ok(),
+                headwords: headwords
+                    .split_whitespace()
+                    .filter_map(parse_headword)
+                    .collect(),
+            })
+        })
+        .collect()
+}
+
+/// Reads the text of the sentences from the `sentences.csv` export, with the
+/// id, the language and the text of each sentence separated by tabs. Only the
+/// sentences with the given ids are kept.
+pub fn read_sentences<R: BufRead>(reader: R, ids: &HashSet<u64>) -> Result<HashMap<u64, String>> {
+    let mut sentences = HashMap::new();
+
+    for line in reader.lines() {
+        let line = line?;
+        let mut fields = line.splitn(3, '\t');
+
+        let (Some(id), Some(_), Some(text)) = (fields.next(), fields.next(), fields.next()) else {
+            continue;
+        };
+
+        if let Ok(id) = id.parse::<u64>() {
+            if ids.contains(&id) {
+                sentences.insert(id, text.to_string());
+            }
+        }
+    }
+
+    Ok(sentences)
+}
+
+/// Ids of the sentences and of the translations of the entries.
+pub fn sentence_ids(entries: &[IndexEntry]) -> HashSet<u64> {
+    entries
+        .iter()
+        .flat_map(|entry| [Some(entry.sentence_id), entry.translation_id])
+        .flatten()
+        .collect()
+}
+
+/// Links the sentences to the words of their headwords, keeping the checked
+/// examples and the shortest ones first.
+pub fn link_examples(
+    entries: &[IndexEntry],
+    sentences: &HashMap<u64, String>,
+    words: &[Word],
+) -> ExampleMap {
+    let mut forms = HashMap::<&str, Vec<&Word>>::new();
+
+    for word in words {
+        let texts = word.kanji.iter().map(|k| &k.text);
+
+        for text in texts.chain(word.kana.iter().map(|k| &k.text)) {
+            forms.entry(text).or_default().push(word);
+        }
+    }
+
+    let mut linked = HashMap::<&str, Vec<(bool, &str, Option<&str>)>>::new();
+
+    for entry in entries {
+        let Some(japanese) = sentences.get(&entry.sentence_id) else {
+            continue;
+        };
+
+        let translation = entry
+            .translation_id
+            .and_then(|id| sentences.get(&id))
+            .map(String::as_str);
+
+        for headword in &entry.headwords {
+            let Some(word) = find_word(&forms, headword) else {
+                continue;
+            };
+
+            let examples = linked.entry(&word.id).or_default();
+
+            if !examples.iter().any(|(_, text, _)| text == japanese) {
+                examples.push((headword.checked, japanese, translation));
+            }
+        }
+    }
+
+    linked
+        .into_iter()
+        .map(|(id, mut examples)| {
+            examples.sort_by_key(|(checked, text, _)| (!checked, text.chars().count()));
+
+            let examples = examples
+                .into_iter()
+                .take(MAX_EXAMPLES)
+                .map(|(_, japanese, translation)| Example {
+                    japanese: japanese.to_string(),
+                    translation: translation.map(String::from),
+                })
+                .collect();
+
+            (id.to_string(), examples)
+        })
+        .collect()
+}
+
+/// Word written as the headword, with its reading if given, preferring the
+/// common words.
+fn find_word<'a>(forms: &HashMap<&str, Vec<&'a Word>>, headword: &Headword) -> Option<&'a Word> {
+    forms
+        .get(headword.text.as_str())?
+        .iter()
+        .filter(|word| {
+            headword
+                .reading
+                .as_ref()
+                .is_none_or(|reading| word.kana.iter().any(|k| k.text == *reading))
+        })
+        .min_by_key(|word| (!word.is_common(), word.id.as_str()))
+        .copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+
+    use test_case::test_case;
+
+    use super::*;
+    use crate::models::jmdict::JMdict;
+
+    fn headword(text: &str, reading: Option<&str>, form: Option<&str>, checked: bool) -> Headword {
+        Headword {
+            text: text.to_string(),
+            reading: reading.map(String::from),
+            sense: None,
+            form: form.map(String::from),
+            checked,
+        }
+    }
+
+    #[test_case("日本", headword("日本", None, None, false) ; "plain")]
+    #[test_case("日本(にっぽん)", headword("日本", Some("にっぽん"), None, false) ; "reading")]
+    #[test_case("食べる{食べた}~", headword("食べる", None, Some("食べた"), true) ; "form checked")]
+    fn test_parse_headword(token: &str, expected: Headword) {
+        assert_eq!(parse_headword(token), Some(expected));
+    }
+
+    #[test]
+    fn test_parse_headword_sense() {
+        let parsed = parse_headword("自由(じゆう)[02]").unwrap();
+
+        assert_eq!(parsed.reading.as_deref(), Some("じゆう"));
+        assert_eq!(parsed.sense, Some(2));
+        assert!(parse_headword("日本(にほん").is_none());
+    }
+
+    #[test]
+    fn test_parse_indices() {
+        let content = "1\t2\t日本 食べる{食べた}~\n3\t-1\t自由\n";
+
+        let entries = parse_indices(content).unwrap();
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].translation_id, Some(2));
+        assert_eq!(entries[0].headwords.len(), 2);
+        assert_eq!(entries[1].translation_id, None);
+        assert!(parse_indices("1\tx\t日本").is_err());
+    }
+
+    #[test]
+    fn test_read_sentences() {
+        let content = "1\tjpn\t日本で食べた。\n2\teng\tI ate in Japan.\n4\tfra\tJ'ai mangé.\n";
+        let ids = HashSet::from([1, 2, 3]);
+
+        let sentences = read_sentences(content.as_bytes(), &ids).unwrap();
+
+        assert_eq!(
+            sentences,
+            HashMap::from([
+                (1, "日本で食べた。".to_string()),
+                (2, "I ate in Japan.".to_string())
+            ])
+        );
+    }
+
+    #[test]
+    fn test_link_examples() {
+        let fixtures_path = Path::new("tests").join("fixtures").join("words.json");
+        let words = fs::read_to_string(fixtures_path).unwrap();
+        let data: JMdict = serde_json::from_str(&words).unwrap();
+
+        let entries =
+            parse_indices("1\t2\t日本 食べる{食べた}\n3\t-1\t食べる~\n5\t-1\t未知\n").unwrap();
+        let sentences = HashMap::from([
+            (1, "日本で食べた。".to_string()),
+            (2, "I ate in Japan.".to_string()),
+            (3, "食べる？".to_string()),
+        ]);
+
+        let examples = link_examples(&entries, &sentences, &data.words);
+
+        assert_eq!(examples.len(), 2);
+        assert_eq!(
+            examples["1582710"],
+            vec![Example {
+                japanese: "日本で食べた。".into(),
+                translation: Some("I ate in Japan.".into()),
+            }]
+        );
+        // the checked example comes first
+        assert_eq!(examples["1358280"][0].japanese, "食べる？");
+        assert_eq!(examples["1358280"][1].japanese, "日本で食べた。");
+    }
+}
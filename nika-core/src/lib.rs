@@ -1,6 +1,7 @@
+pub mod analysis;
 pub mod controllers;
 pub mod errors;
 pub mod importer;
 pub mod models;
 pub mod repositories;
-mod utils;
+pub mod utils;
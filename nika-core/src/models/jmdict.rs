@@ -37,12 +37,17 @@ pub struct Kana {
     pub common: bool,
     pub tags: Vec<Tag>,
     pub text: String,
+    /// Pitch accent patterns of the reading, imported from an accent
+    /// dictionary: the mora after which the pitch drops, 0 when it does not.
+    #[serde(default)]
+    pub pitch_accents: Vec<u8>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Hash, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct Sense {
-    // pub antonym: Vec<Xref>,
+    #[serde(with = "xrefs")]
+    pub antonym: Vec<Xref>,
     pub applies_to_kana: Vec<String>,
     pub applies_to_kanji: Vec<String>,
     pub dialect: Vec<Tag>,
@@ -52,7 +57,8 @@ pub struct Sense {
     pub language_source: Vec<LanguageSource>,
     pub misc: Vec<Tag>,
     pub part_of_speech: Vec<Tag>,
-    // pub related: Vec<Xref>,
+    #[serde(with = "xrefs")]
+    pub related: Vec<Xref>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Hash, PartialEq, Eq)]
@@ -68,6 +74,66 @@ pub enum Xref {
     Word((String,)),
 }
 
+impl Xref {
+    /// Text of the word, its reading and the index of the sense (from 1).
+    pub fn parts(&self) -> (&str, Option<&str>, Option<u64>) {
+        match self {
+            Self::WordReadingIndex((word, reading, index)) => (word, Some(reading), Some(*index)),
+            Self::WordReading((word, reading)) => (word, Some(reading), None),
+            Self::WordIndex((word, index)) => (word, None, Some(*index)),
+            Self::Word((word,)) => (word, None, None),
+        }
+    }
+
+    pub fn from_parts(word: String, reading: Option<String>, index: Option<u64>) -> Self {
+        match (reading, index) {
+            (Some(reading), Some(index)) => Self::WordReadingIndex((word, reading, index)),
+            (Some(reading), None) => Self::WordReading((word, reading)),
+            (None, Some(index)) => Self::WordIndex((word, index)),
+            (None, None) => Self::Word((word,)),
+        }
+    }
+}
+
+/// The variants of the cross-references are told apart by their shape in the
+/// JMdict JSON, which the binary formats can't do, so they are stored as the
+/// parts of the reference.
+mod xrefs {
+    use serde::Deserialize;
+    use serde::Deserializer;
+    use serde::Serialize;
+    use serde::Serializer;
+
+    use super::Xref;
+
+    type Parts = (String, Option<String>, Option<u64>);
+
+    pub fn serialize<S: Serializer>(xrefs: &[Xref], serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            return xrefs.serialize(serializer);
+        }
+
+        xrefs
+            .iter()
+            .map(Xref::parts)
+            .collect::<Vec<(&str, Option<&str>, Option<u64>)>>()
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<Xref>, D::Error> {
+        if deserializer.is_human_readable() {
+            return Vec::<Xref>::deserialize(deserializer);
+        }
+
+        let parts = Vec::<Parts>::deserialize(deserializer)?;
+
+        Ok(parts
+            .into_iter()
+            .map(|(word, reading, index)| Xref::from_parts(word, reading, index))
+            .collect())
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Hash, PartialEq, Eq)]
 pub struct LanguageSource {
     pub full: bool,
@@ -102,6 +168,61 @@ pub struct Gloss {
     pub gloss_type: Option<GlossType>,
 }
 
+impl Word {
+    /// Whether any of the forms of the word is common.
+    pub fn is_common(&self) -> bool {
+        self.kanji.iter().any(|k| k.common) || self.kana.iter().any(|k| k.common)
+    }
+
+    /// Kanji forms of the word paired with each of their kana readings, in the
+    /// order of the readings.
+    pub fn kanji_kana_pairs(&self) -> Vec<(&Kanji, &Kana)> {
+        let mut pairs = Vec::new();
+
+        for kana in &self.kana {
+            for a in &kana.applies_to_kanji {
+                if a == "*" {
+                    pairs.extend(self.kanji.iter().map(|kanji| (kanji, kana)));
+                } else if let Some(kanji) = self.kanji.iter().find(|k| k.text == *a) {
+                    pairs.push((kanji, kana));
+                }
+            }
+        }
+
+        pairs
+    }
+
+    /// Keeps only the glosses written in one of the given languages. The
+    /// senses left without any gloss are kept, as the cross-references and the
+    /// examples point to the senses by their number.
+    pub fn retain_languages(&mut self, languages: &[Language3Letter]) {
+        if languages.is_empty() {
+            return;
+        }
+
+        for sense in &mut self.sense {
+            sense.gloss.retain(|gloss| languages.contains(&gloss.lang));
+        }
+    }
+}
+
+impl Sense {
+    /// Returns the glosses written in the given languages, ordered by language
+    /// preference.
+    ///
+    /// All the glosses are returned when no language is given.
+    pub fn glosses(&self, languages: &[Language3Letter]) -> Vec<&Gloss> {
+        if languages.is_empty() {
+            return self.gloss.iter().collect();
+        }
+
+        languages
+            .iter()
+            .flat_map(|lang| self.gloss.iter().filter(move |gloss| gloss.lang == *lang))
+            .collect()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use serde_json::Error;
@@ -145,6 +266,27 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_xrefs_bincode() {
+        let mut sense = sense(vec![gloss("eng", "circle")]);
+        sense.related = vec![
+            Xref::WordReadingIndex(("丸".to_string(), "まる".to_string(), 1)),
+            Xref::WordReading(("丸".to_string(), "まる".to_string())),
+            Xref::WordIndex(("丸".to_string(), 2)),
+            Xref::Word(("丸".to_string(),)),
+        ];
+        sense.antonym = vec![Xref::Word(("四角".to_string(),))];
+
+        let bytes = bincode::serialize(&sense).unwrap();
+
+        assert_eq!(bincode::deserialize::<Sense>(&bytes).unwrap(), sense);
+
+        let json = serde_json::to_string(&sense).unwrap();
+
+        assert!(json.contains(r#""antonym":[["四角"]]"#));
+        assert_eq!(serde_json::from_str::<Sense>(&json).unwrap(), sense);
+    }
+
     #[test]
     fn test_related() {
         let json_str = r#"{"related": [["どの"], ["その", 1]]}"#;
@@ -157,4 +299,105 @@ mod tests {
         assert_eq!(related[0], Xref::Word(("どの".to_string(),)));
         assert_eq!(related[1], Xref::WordIndex(("その".to_string(), 1)));
     }
+
+    fn gloss(lang: &str, text: &str) -> Gloss {
+        Gloss {
+            gender: None,
+            lang: lang.to_string(),
+            text: text.to_string(),
+            gloss_type: None,
+        }
+    }
+
+    fn sense(gloss: Vec<Gloss>) -> Sense {
+        Sense {
+            antonym: vec![],
+            applies_to_kana: vec![],
+            applies_to_kanji: vec![],
+            dialect: vec![],
+            field: vec![],
+            gloss,
+            info: vec![],
+            language_source: vec![],
+            misc: vec![],
+            part_of_speech: vec![],
+            related: vec![],
+        }
+    }
+
+    #[test]
+    fn test_glosses_language_order() {
+        let sense = sense(vec![
+            gloss("eng", "to eat"),
+            gloss("ger", "essen"),
+            gloss("spa", "comer"),
+            gloss("eng", "to live on"),
+        ]);
+
+        let texts = |langs: &[String]| {
+            sense
+                .glosses(langs)
+                .iter()
+                .map(|g| g.text.as_str())
+                .collect::<Vec<&str>>()
+        };
+
+        assert_eq!(
+            texts(&["ger".into(), "eng".into()]),
+            vec!["essen", "to eat", "to live on"]
+        );
+        assert_eq!(texts(&["spa".into()]), vec!["comer"]);
+        assert_eq!(texts(&["fre".into()]), Vec::<&str>::new());
+        assert_eq!(texts(&[]).len(), 4);
+    }
+
+    #[test]
+    fn test_retain_languages() {
+        let mut word = Word {
+            id: "1358280".into(),
+            kana: vec![],
+            kanji: vec![],
+            sense: vec![
+                sense(vec![gloss("eng", "to eat"), gloss("ger", "essen")]),
+                sense(vec![gloss("ger", "leben von")]),
+            ],
+        };
+
+        word.retain_languages(&["eng".into()]);
+
+        assert_eq!(word.sense.len(), 2);
+        assert_eq!(word.sense[0].gloss, vec![gloss("eng", "to eat")]);
+        assert!(word.sense[1].gloss.is_empty());
+    }
+
+    #[test]
+    fn test_kanji_kana_pairs() {
+        let kanji = |text: &str| Kanji {
+            common: false,
+            tags: vec![],
+            text: text.into(),
+        };
+        let kana = |text: &str, applies_to_kanji: &[&str]| Kana {
+            applies_to_kanji: applies_to_kanji.iter().map(|a| a.to_string()).collect(),
+            common: false,
+            tags: vec![],
+            text: text.into(),
+            pitch_accents: vec![],
+        };
+
+        let word = Word {
+            id: "1".into(),
+            kana: vec![kana("ひとつ", &["一つ"]), kana("いち", &["一"])],
+            kanji: vec![kanji("一"), kanji("一つ")],
+            sense: vec![],
+        };
+
+        let pairs = word
+            .kanji_kana_pairs()
+            .iter()
+            .map(|(kanji, kana)| (kanji.text.as_str(), kana.text.as_str()))
+            .collect::<Vec<(&str, &str)>>();
+
+        assert_eq!(pairs, vec![("一つ", "ひとつ"), ("一", "いち")]);
+    }
 }
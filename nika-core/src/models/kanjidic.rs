@@ -1,6 +1,8 @@
 use serde_derive::Deserialize;
 use serde_derive::Serialize;
 
+use super::jmdict::Language3Letter;
+
 pub type Language2Letter = String;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -17,10 +19,138 @@ pub struct Kanji {
     pub misc: Misc,
     pub literal: String,
     pub reading_meaning: Option<ReadingMeaning>,
-    // queryCodes
-    // radicals
-    // codepoints
-    // dictionaryReferences
+    pub query_codes: Vec<QueryCode>,
+    pub radicals: Vec<Radical>,
+    pub codepoints: Vec<Codepoint>,
+    pub dictionary_references: Vec<DictionaryReference>,
+}
+
+/// Code identifying a kanji, used to look it up.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KanjiCode {
+    /// SKIP code, e.g. `3-3-1`.
+    Skip(String),
+    /// Four-corner code, with or without the fifth corner, e.g. `6010`.
+    FourCorner(String),
+    /// Number of the classical (Kangxi) radical, e.g. 72 for 日.
+    Radical(u16),
+    /// Unicode or JIS codepoint, e.g. `U+65E5` or `1-38-92`.
+    Codepoint(String),
+    /// Index in a dictionary or a textbook, e.g. `heisig` and `12`.
+    Reference(String, String),
+}
+
+impl Kanji {
+    pub fn matches(&self, code: &KanjiCode) -> bool {
+        match code {
+            // the codes of the common mistakes find the kanji too
+            KanjiCode::Skip(skip) => self
+                .query_codes
+                .iter()
+                .any(|code| code.kind == "skip" && code.value == *skip),
+            KanjiCode::FourCorner(four_corner) => self
+                .query_codes("four_corner")
+                .iter()
+                .any(|value| value == four_corner || value.split('.').next() == Some(four_corner)),
+            KanjiCode::Radical(number) => self.classical_radical() == Some(*number),
+            KanjiCode::Codepoint(codepoint) => {
+                let codepoint = codepoint.trim_start_matches("U+").trim_start_matches("u+");
+
+                self.codepoints
+                    .iter()
+                    .any(|c| c.value.eq_ignore_ascii_case(codepoint))
+            }
+            KanjiCode::Reference(kind, value) => self
+                .dictionary_references
+                .iter()
+                .any(|r| r.kind == *kind && r.value == *value),
+        }
+    }
+
+    /// Values of the query codes of the type, e.g. `skip`.
+    pub fn query_codes(&self, kind: &str) -> Vec<&str> {
+        self.query_codes
+            .iter()
+            .filter(|code| code.kind == kind && code.skip_misclassification.is_none())
+            .map(|code| code.value.as_str())
+            .collect()
+    }
+
+    /// Number of the Kangxi radical the kanji is classified under.
+    pub fn classical_radical(&self) -> Option<u16> {
+        self.radicals
+            .iter()
+            .find(|radical| radical.kind == "classical")
+            .map(|radical| radical.value)
+    }
+
+    /// Index of the kanji in the dictionary of the type, e.g. `heisig`.
+    pub fn reference(&self, kind: &str) -> Option<&str> {
+        self.dictionary_references
+            .iter()
+            .find(|reference| reference.kind == kind)
+            .map(|reference| reference.value.as_str())
+    }
+
+    /// On'yomi readings, in katakana.
+    pub fn on_readings(&self) -> Vec<&str> {
+        self.readings(ReadingType::JapaneseOn)
+    }
+
+    /// Kun'yomi readings, in hiragana with the okurigana after a dot.
+    pub fn kun_readings(&self) -> Vec<&str> {
+        self.readings(ReadingType::JapaneseKun)
+    }
+
+    /// Meanings in the languages, or in English when there are none in them.
+    pub fn meanings(&self, languages: &[Language3Letter]) -> Vec<&str> {
+        let meanings = self
+            .reading_meaning
+            .iter()
+            .flat_map(|rm| &rm.groups)
+            .flat_map(|group| &group.meanings);
+
+        let in_languages = meanings
+            .clone()
+            .filter(|meaning| {
+                languages
+                    .iter()
+                    .any(|lang| to_2_letter(lang) == Some(meaning.lang.as_str()))
+            })
+            .map(|meaning| meaning.value.as_str())
+            .collect::<Vec<&str>>();
+
+        if !in_languages.is_empty() {
+            return in_languages;
+        }
+
+        meanings
+            .filter(|meaning| meaning.lang == "en")
+            .map(|meaning| meaning.value.as_str())
+            .collect()
+    }
+
+    pub fn readings(&self, kind: ReadingType) -> Vec<&str> {
+        self.reading_meaning
+            .iter()
+            .flat_map(|rm| &rm.groups)
+            .flat_map(|group| &group.readings)
+            .filter(|reading| reading.kind == kind)
+            .map(|reading| reading.value.as_str())
+            .collect()
+    }
+}
+
+/// Kanjidic language of the JMdict language, for the ones with meanings in
+/// Kanjidic.
+fn to_2_letter(lang: &str) -> Option<&'static str> {
+    match lang {
+        "eng" => Some("en"),
+        "fre" => Some("fr"),
+        "spa" => Some("es"),
+        "por" => Some("pt"),
+        _ => None,
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -42,20 +172,27 @@ pub struct Meaning {
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
-#[serde(tag = "type", content = "value")]
-pub enum Reading {
+pub struct Reading {
+    // a struct rather than an enum tagged by type, which bincode can't read
+    #[serde(rename = "type")]
+    pub kind: ReadingType,
+    pub value: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReadingType {
     #[serde(rename = "ja_on")]
-    JapaneseOn(String),
+    JapaneseOn,
     #[serde(rename = "ja_kun")]
-    JapaneseKun(String),
+    JapaneseKun,
     #[serde(rename = "korean_r")]
-    KoreanRomanji(String),
+    KoreanRomanji,
     #[serde(rename = "korean_h")]
-    KoreanHangul(String),
+    KoreanHangul,
     #[serde(rename = "pinyin")]
-    Pinyin(String),
+    Pinyin,
     #[serde(rename = "vietnam")]
-    Vietnam(String),
+    Vietnam,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -65,6 +202,127 @@ pub struct Misc {
     pub frequency: Option<u16>,
     pub jlpt_level: Option<u8>,
     pub stroke_counts: Vec<u8>,
-    // variants
-    // radical_names
+    pub variants: Vec<Variant>,
+    /// Names of the kanji when it is a radical, e.g. みずへん.
+    pub radical_names: Vec<String>,
+}
+
+// the types are kept as strings, as new ones are added to Kanjidic over time
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryCode {
+    /// e.g. `skip`, `four_corner` or `sh_desc`.
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// How the SKIP code is wrong, for the codes of common mistakes.
+    pub skip_misclassification: Option<String>,
+    pub value: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Radical {
+    /// `classical` (Kangxi) or `nelson_c`.
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub value: u16,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Codepoint {
+    /// `ucs`, `jis208`, `jis212` or `jis213`.
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub value: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct DictionaryReference {
+    /// e.g. `heisig`, `nelson_c` or `moro`.
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub morohashi: Option<Morohashi>,
+    pub value: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Morohashi {
+    pub volume: u16,
+    pub page: u16,
+}
+
+/// Other form of the kanji, given by one of its codes.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Variant {
+    /// Type of the code, e.g. `jis208` or `ucs`.
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub value: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+
+    use test_case::test_case;
+
+    use super::*;
+
+    fn kanji(literal: &str) -> Kanji {
+        let fixtures_path = Path::new("tests").join("fixtures").join("kanjidic.json");
+        let content = fs::read_to_string(fixtures_path).unwrap();
+        let kanjidic: Kanjidic = serde_json::from_str(&content).unwrap();
+
+        kanjidic
+            .characters
+            .into_iter()
+            .find(|k| k.literal == literal)
+            .unwrap()
+    }
+
+    #[test_case(KanjiCode::Skip("3-3-4".into()) ; "skip")]
+    #[test_case(KanjiCode::Skip("2-3-4".into()) ; "misclassified skip")]
+    #[test_case(KanjiCode::FourCorner("6040.0".into()) ; "four corner")]
+    #[test_case(KanjiCode::FourCorner("6040".into()) ; "four corner without fifth")]
+    #[test_case(KanjiCode::Radical(31) ; "radical")]
+    #[test_case(KanjiCode::Codepoint("U+56F3".into()) ; "unicode")]
+    #[test_case(KanjiCode::Codepoint("1-31-62".into()) ; "jis")]
+    #[test_case(KanjiCode::Reference("heisig".into(), "555".into()) ; "heisig")]
+    fn test_matches(code: KanjiCode) {
+        let kanji = kanji("図");
+
+        assert!(kanji.matches(&code));
+        assert!(!kanji.matches(&KanjiCode::Radical(72)));
+    }
+
+    #[test]
+    fn test_codes() {
+        let kanji = kanji("図");
+
+        assert_eq!(kanji.query_codes("skip"), vec!["3-3-4"]);
+        assert_eq!(kanji.classical_radical(), Some(31));
+        assert_eq!(kanji.reference("heisig"), Some("555"));
+        assert_eq!(kanji.reference("moro"), None);
+    }
+
+    #[test]
+    fn test_readings() {
+        let kanji = kanji("食");
+
+        assert_eq!(kanji.on_readings(), vec!["ショク", "ジキ"]);
+        assert_eq!(
+            kanji.kun_readings(),
+            vec!["く.う", "く.らう", "た.べる", "は.む"]
+        );
+    }
+
+    #[test_case(&["eng"], &["book", "present", "main", "true", "real"] ; "english")]
+    #[test_case(&["fre"], &["livre", "origine"] ; "french")]
+    #[test_case(&["ger"], &["book", "present", "main", "true", "real"] ; "fallback")]
+    fn test_meanings(languages: &[&str], expected: &[&str]) {
+        let languages = languages.iter().map(|l| l.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(kanji("本").meanings(&languages), expected);
+    }
 }
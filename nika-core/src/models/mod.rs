@@ -1,5 +1,6 @@
 pub mod jmdict;
 pub mod kanjidic;
 pub mod link;
+pub mod radicals;
 pub mod study_list;
 pub mod user_config;
@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+
+use serde_derive::Deserialize;
+use serde_derive::Serialize;
+
+/// Radicals written in RADKFILE with a kanji containing them, as the radicals
+/// missing from JIS X 0208 can't be written themselves.
+const SUBSTITUTES: [(&str, &str); 21] = [
+    ("化", "亻"),
+    ("刈", "刂"),
+    ("込", "辶"),
+    ("汁", "氵"),
+    ("犯", "犭"),
+    ("艾", "艹"),
+    ("邦", "⻏"),
+    ("阡", "⻖"),
+    ("尚", "⺌"),
+    ("忙", "忄"),
+    ("扎", "扌"),
+    ("礼", "礻"),
+    ("初", "衤"),
+    ("老", "耂"),
+    ("杰", "灬"),
+    ("个", "𠆢"),
+    ("并", "丷"),
+    ("乞", "𠂉"),
+    ("禹", "禸"),
+    ("買", "罒"),
+    ("滴", "啇"),
+];
+
+/// Other forms of the radicals that may be typed: the plain 阝 for both of its
+/// sides, and the forms of the CJK radicals blocks.
+const ALIASES: [(&str, &[&str]); 15] = [
+    ("阝", &["⻏", "⻖"]),
+    ("⺅", &["亻"]),
+    ("⺉", &["刂"]),
+    ("⻌", &["辶"]),
+    ("⻍", &["辶"]),
+    ("⺡", &["氵"]),
+    ("⺨", &["犭"]),
+    ("⺾", &["艹"]),
+    ("⺖", &["忄"]),
+    ("⺘", &["扌"]),
+    ("⺭", &["礻"]),
+    ("⻂", &["衤"]),
+    ("⺹", &["耂"]),
+    ("⺣", &["灬"]),
+    ("⺲", &["罒"]),
+];
+
+/// KRADFILE, with the components of each kanji.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Kradfile {
+    pub version: String,
+    pub kanji: HashMap<String, Vec<String>>,
+}
+
+/// RADKFILE, with the kanji containing each radical.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Radkfile {
+    pub version: String,
+    pub radicals: HashMap<String, Radical>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Radical {
+    pub stroke_count: u8,
+    /// JIS code of the image of the radical, for the substituted ones.
+    pub code: Option<String>,
+    pub kanji: Vec<String>,
+}
+
+/// Components of the kanji and kanji of the radicals, merged from KRADFILE
+/// and RADKFILE.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct Radicals {
+    pub components: HashMap<String, Vec<String>>,
+    pub radicals: HashMap<String, Radical>,
+}
+
+impl Radicals {
+    pub fn new(kradfile: Kradfile, radkfile: Radkfile) -> Self {
+        Self {
+            components: kradfile.kanji,
+            radicals: radkfile.radicals,
+        }
+    }
+}
+
+/// Radicals as written in RADKFILE, e.g. 汁 for 氵, or 邦 and 阡 for 阝.
+pub fn to_radkfile(radical: &str) -> Vec<&str> {
+    let shown = ALIASES
+        .iter()
+        .find(|(typed, _)| *typed == radical)
+        .map_or(vec![radical], |(_, shown)| shown.to_vec());
+
+    shown
+        .into_iter()
+        .map(|shown| {
+            SUBSTITUTES
+                .iter()
+                .find(|(_, s)| *s == shown)
+                .map_or(shown, |(written, _)| written)
+        })
+        .collect()
+}
+
+/// Radical as shown, e.g. 氵 for 汁.
+pub fn from_radkfile(radical: &str) -> &str {
+    SUBSTITUTES
+        .iter()
+        .find(|(written, _)| *written == radical)
+        .map_or(radical, |(_, shown)| shown)
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    #[test_case("氵", "汁" ; "substituted")]
+    #[test_case("木", "木" ; "written")]
+    fn test_to_radkfile(radical: &str, expected: &str) {
+        assert_eq!(to_radkfile(radical), vec![expected]);
+        assert_eq!(from_radkfile(expected), radical);
+    }
+
+    #[test_case("阝", vec!["邦", "阡"] ; "both sides")]
+    #[test_case("⺅", vec!["化"] ; "radical block")]
+    fn test_to_radkfile_aliases(radical: &str, expected: Vec<&str>) {
+        assert_eq!(to_radkfile(radical), expected);
+    }
+}
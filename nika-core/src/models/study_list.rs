@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
 
@@ -21,11 +22,46 @@ impl Default for StudyConfig {
     }
 }
 
+/// Study status of an item, the items without status being new.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ItemStatus {
+    Learning,
+    Known,
+    Skipped,
+}
+
+/// What the items of a list are: JMdict word ids or kanji literals.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ItemKind {
+    #[default]
+    Word,
+    Kanji,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct StudyList {
     pub name: String,
     pub config: StudyConfig,
     pub items: Vec<String>,
+    pub status: HashMap<String, ItemStatus>,
+    pub kind: ItemKind,
+}
+
+/// Lists saved before the kind of the items was kept, all of words.
+#[derive(Deserialize)]
+struct UntypedStudyList {
+    name: String,
+    config: StudyConfig,
+    items: Vec<String>,
+    status: HashMap<String, ItemStatus>,
+}
+
+/// Lists saved before the status of the items was kept.
+#[derive(Deserialize)]
+struct LegacyStudyList {
+    name: String,
+    config: StudyConfig,
+    items: Vec<String>,
 }
 
 impl StudyList {
@@ -34,14 +70,49 @@ impl StudyList {
             name: name.to_string(),
             config: StudyConfig::default(),
             items,
+            status: HashMap::new(),
+            kind: ItemKind::Word,
         }
     }
 
+    pub fn with_kind(mut self, kind: ItemKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    /// Items marked with the status, in study order.
+    pub fn items_with_status(&self, status: ItemStatus) -> Vec<&str> {
+        self.items
+            .iter()
+            .filter(|item| self.status.get(*item) == Some(&status))
+            .map(String::as_str)
+            .collect()
+    }
+
     pub fn load<P: AsRef<Path>>(filepath: &P) -> Result<StudyList> {
         let file = fs::read(filepath)?;
-        let config = bincode::deserialize::<StudyList>(&file)?;
 
-        Ok(config)
+        // the older lists are shorter, so they are tried from the newest one
+        if let Ok(study_list) = bincode::deserialize::<StudyList>(&file) {
+            return Ok(study_list);
+        }
+
+        if let Ok(untyped) = bincode::deserialize::<UntypedStudyList>(&file) {
+            return Ok(StudyList {
+                name: untyped.name,
+                config: untyped.config,
+                items: untyped.items,
+                status: untyped.status,
+                kind: ItemKind::Word,
+            });
+        }
+
+        let legacy = bincode::deserialize::<LegacyStudyList>(&file)?;
+
+        Ok(StudyList {
+            config: legacy.config,
+            ..StudyList::new(&legacy.name, legacy.items)
+        })
     }
 
     pub fn save<P: AsRef<Path>>(filepath: &P, study_list: StudyList) -> Result<()> {
@@ -51,3 +122,89 @@ impl StudyList {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use tempfile::NamedTempFile;
+
+    use super::*;
+
+    #[derive(Serialize)]
+    struct LegacyList<'a> {
+        name: &'a str,
+        config: StudyConfig,
+        items: Vec<&'a str>,
+    }
+
+    #[test]
+    fn test_load_legacy_list() {
+        let file = NamedTempFile::new().unwrap();
+        let legacy = LegacyList {
+            name: "list",
+            config: StudyConfig::default(),
+            items: vec!["1358280", "1582710"],
+        };
+
+        fs::write(file.path(), bincode::serialize(&legacy).unwrap()).unwrap();
+
+        let study_list = StudyList::load(&file.path()).unwrap();
+
+        assert_eq!(
+            study_list,
+            StudyList::new("list", vec!["1358280".into(), "1582710".into()])
+        );
+    }
+
+    #[derive(Serialize)]
+    struct UntypedList<'a> {
+        name: &'a str,
+        config: StudyConfig,
+        items: Vec<&'a str>,
+        status: HashMap<&'a str, ItemStatus>,
+    }
+
+    #[test]
+    fn test_load_untyped_list() {
+        let file = NamedTempFile::new().unwrap();
+        let untyped = UntypedList {
+            name: "list",
+            config: StudyConfig::default(),
+            items: vec!["1358280"],
+            status: HashMap::from([("1358280", ItemStatus::Known)]),
+        };
+
+        fs::write(file.path(), bincode::serialize(&untyped).unwrap()).unwrap();
+
+        let study_list = StudyList::load(&file.path()).unwrap();
+
+        assert_eq!(study_list.kind, ItemKind::Word);
+        assert_eq!(
+            study_list.items_with_status(ItemStatus::Known),
+            vec!["1358280"]
+        );
+    }
+
+    #[test]
+    fn test_save_kanji_list() {
+        let file = NamedTempFile::new().unwrap();
+        let study_list =
+            StudyList::new("kanji", vec!["日".into(), "本".into()]).with_kind(ItemKind::Kanji);
+
+        StudyList::save(&file.path(), study_list.clone()).unwrap();
+
+        assert_eq!(StudyList::load(&file.path()).unwrap(), study_list);
+    }
+
+    #[test]
+    fn test_items_with_status() {
+        let mut study_list = StudyList::new("list", vec!["1".into(), "2".into(), "3".into()]);
+        study_list.status.insert("3".into(), ItemStatus::Known);
+        study_list.status.insert("1".into(), ItemStatus::Known);
+        study_list.status.insert("2".into(), ItemStatus::Skipped);
+
+        assert_eq!(
+            study_list.items_with_status(ItemStatus::Known),
+            vec!["1", "3"]
+        );
+    }
+}
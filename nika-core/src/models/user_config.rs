@@ -5,6 +5,7 @@ use serde_derive::Deserialize;
 use serde_derive::Serialize;
 
 use crate::errors::Result;
+use crate::models::jmdict::Language3Letter;
 use crate::models::link::Link;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -12,12 +13,70 @@ pub struct UserConfig {
     pub dictionaries: Vec<Link>,
     #[serde(default)]
     pub current_list: Option<String>,
+    /// Gloss languages (ISO 639-2) in order of preference.
+    #[serde(default = "default_languages")]
+    pub languages: Vec<Language3Letter>,
+    #[serde(default)]
+    pub update: UpdatePolicy,
+    #[serde(default)]
+    pub display: DisplaySettings,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DisplaySettings {
+    /// Show the romaji next to the kana readings.
+    #[serde(default)]
+    pub romaji: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UpdatePolicy {
+    /// Maximum age of the dictionary in days, `0` disables the check.
+    pub interval_days: u64,
+    /// Unix timestamp (in seconds) of the last successful update.
+    pub last_check: Option<u64>,
+}
+
+impl Default for UpdatePolicy {
+    fn default() -> Self {
+        Self {
+            interval_days: 3,
+            last_check: None,
+        }
+    }
+}
+
+impl UpdatePolicy {
+    const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+    /// Whether the dictionary is older than the configured interval at the
+    /// given unix timestamp. A dictionary never updated is not outdated, as
+    /// there may be none yet.
+    pub fn is_stale(&self, now: u64) -> bool {
+        if self.interval_days == 0 {
+            return false;
+        }
+
+        match self.last_check {
+            Some(last_check) => {
+                now.saturating_sub(last_check) > self.interval_days * Self::SECONDS_PER_DAY
+            }
+            None => false,
+        }
+    }
+}
+
+fn default_languages() -> Vec<Language3Letter> {
+    vec!["eng".into()]
 }
 
 impl Default for UserConfig {
     fn default() -> Self {
         UserConfig {
             current_list: None,
+            languages: default_languages(),
+            update: UpdatePolicy::default(),
+            display: DisplaySettings::default(),
             dictionaries: vec![
                 Link {
                     text: "Jisho.org".into(),
@@ -60,3 +119,43 @@ impl UserConfig {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    const DAY: u64 = 24 * 60 * 60;
+
+    #[test_case(3, None, 0, false ; "never updated")]
+    #[test_case(3, Some(0), 3 * DAY, false ; "within interval")]
+    #[test_case(3, Some(0), 3 * DAY + 1, true ; "older than interval")]
+    #[test_case(0, None, 0, false ; "check disabled")]
+    #[test_case(1, Some(DAY), 0, false ; "clock skew")]
+    fn test_is_stale(interval_days: u64, last_check: Option<u64>, now: u64, expected: bool) {
+        let policy = UpdatePolicy {
+            interval_days,
+            last_check,
+        };
+
+        assert_eq!(policy.is_stale(now), expected);
+    }
+
+    #[test]
+    fn test_missing_fields_default() {
+        let config = toml::from_str::<UserConfig>("dictionaries = []").unwrap();
+
+        assert_eq!(config.languages, vec!["eng".to_string()]);
+        assert_eq!(config.update, UpdatePolicy::default());
+        assert_eq!(config.display, DisplaySettings::default());
+    }
+
+    #[test]
+    fn test_display_settings() {
+        let config =
+            toml::from_str::<UserConfig>("dictionaries = []\n[display]\nromaji = true").unwrap();
+
+        assert!(config.display.romaji);
+    }
+}
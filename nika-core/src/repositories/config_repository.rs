@@ -1,7 +1,10 @@
 use std::path::PathBuf;
 
 use crate::errors::Result;
+use crate::models::jmdict::Language3Letter;
 use crate::models::link::Link;
+use crate::models::user_config::DisplaySettings;
+use crate::models::user_config::UpdatePolicy;
 use crate::models::user_config::UserConfig;
 
 pub struct ConfigRepository {
@@ -18,6 +21,27 @@ impl ConfigRepository {
         Ok(config.dictionaries.clone())
     }
 
+    pub fn languages(&self) -> Result<Vec<Language3Letter>> {
+        let config = self.load_config()?;
+        Ok(config.languages)
+    }
+
+    pub fn update_policy(&self) -> Result<UpdatePolicy> {
+        let config = self.load_config()?;
+        Ok(config.update)
+    }
+
+    pub fn display_settings(&self) -> Result<DisplaySettings> {
+        let config = self.load_config()?;
+        Ok(config.display)
+    }
+
+    pub fn set_last_check(&self, timestamp: u64) -> Result<()> {
+        let mut config = self.load_config()?;
+        config.update.last_check = Some(timestamp);
+        self.save_config(config)
+    }
+
     pub fn set_current_list(&self, name: &str) -> Result<()> {
         let mut config = self.load_config()?;
         config.current_list = Some(name.to_string());
@@ -1,39 +1,173 @@
 #![allow(unused)]
 
+use std::cmp::Reverse;
 use std::collections::HashMap;
+use std::collections::HashSet;
 use std::fs;
+use std::ops::Range;
 use std::path::Path;
 
 use bincode::Error;
+use bincode::ErrorKind;
+use once_cell::sync::OnceCell;
 use rand::seq::SliceRandom;
 use rayon::prelude::*;
+use serde::de::DeserializeOwned;
 
+use crate::importer::frequency::FrequencyMap;
+use crate::importer::kanjivg::StrokeMap;
+use crate::importer::tatoeba::Example;
+use crate::importer::tatoeba::ExampleMap;
 use crate::models::jmdict::Kanji;
 use crate::models::jmdict::Tag;
 use crate::models::jmdict::Word;
+use crate::models::jmdict::Xref;
+use crate::models::kanjidic;
+use crate::models::kanjidic::KanjiCode;
+use crate::models::kanjidic::Kanjidic;
+use crate::models::radicals;
+use crate::models::radicals::Radicals;
+use crate::utils::japanese::JapaneseCharacter;
+use crate::utils::japanese::JapaneseString;
 
 pub type WordMap = HashMap<String, Word>;
 pub type TagMap = HashMap<Tag, String>;
+pub type KanjiMap = HashMap<String, kanjidic::Kanji>;
+
+/// Words that may be confused with a word or used in its place.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct SimilarWords<'a> {
+    /// Words with the same reading.
+    pub homophones: Vec<&'a Word>,
+    /// Words written with any of the kanji of the word, from the ones sharing
+    /// the most kanji.
+    pub shared_kanji: Vec<&'a Word>,
+    /// Words with the same glosses, from the ones sharing the most glosses.
+    pub synonyms: Vec<&'a Word>,
+}
 
 #[derive(Debug)]
 pub struct DictionaryRepository {
     dictionary: WordMap,
     tags: TagMap,
+    frequencies: FrequencyMap,
+    examples: ExampleMap,
+    kanji: KanjiMap,
+    radicals: Radicals,
+    strokes: StrokeMap,
+    /// Ids of the words of each kanji and kana form, built when first needed.
+    forms: OnceCell<HashMap<String, Vec<String>>>,
+    /// Normalized forms of the search, built when first needed.
+    search_index: OnceCell<SearchIndex>,
+}
+
+/// Ids of the words by normalized form, regardless of the kana script, the
+/// width and the long vowels.
+#[derive(Debug, Default)]
+struct SearchIndex {
+    /// Ids of the words by normalized kanji form.
+    kanji: HashMap<String, Vec<String>>,
+    /// Normalized kanji forms by character, to find the forms containing a
+    /// text without going through all of them.
+    kanji_chars: HashMap<char, Vec<String>>,
+    /// Ids of the words by normalized kana form.
+    kana: HashMap<String, Vec<String>>,
 }
 
 impl DictionaryRepository {
     pub fn new<P: AsRef<Path>>(words_bin_path: &P, tags_bin_path: &P) -> Result<Self, Error> {
-        let dict = bincode::deserialize::<WordMap>(&fs::read(words_bin_path)?)?;
-        let tags = bincode::deserialize::<TagMap>(&fs::read(tags_bin_path)?)?;
+        let dict = read_bin::<WordMap, P>(words_bin_path)?;
+        let tags = read_bin::<TagMap, P>(tags_bin_path)?;
 
         Ok(DictionaryRepository {
             dictionary: dict,
             tags,
+            frequencies: FrequencyMap::new(),
+            examples: ExampleMap::new(),
+            kanji: KanjiMap::new(),
+            radicals: Radicals::default(),
+            strokes: StrokeMap::new(),
+            forms: OnceCell::new(),
+            search_index: OnceCell::new(),
         })
     }
 
     pub fn from(dictionary: WordMap, tags: TagMap) -> Self {
-        DictionaryRepository { dictionary, tags }
+        DictionaryRepository {
+            dictionary,
+            tags,
+            frequencies: FrequencyMap::new(),
+            examples: ExampleMap::new(),
+            kanji: KanjiMap::new(),
+            radicals: Radicals::default(),
+            strokes: StrokeMap::new(),
+            forms: OnceCell::new(),
+            search_index: OnceCell::new(),
+        }
+    }
+
+    /// Loads the frequency ranks of the words, when they were generated.
+    pub fn with_frequencies<P: AsRef<Path>>(
+        mut self,
+        frequencies_bin_path: &P,
+    ) -> Result<Self, Error> {
+        if frequencies_bin_path.as_ref().exists() {
+            self.frequencies = read_bin(frequencies_bin_path)?;
+        }
+
+        Ok(self)
+    }
+
+    /// Loads the example sentences of the words, when they were imported.
+    pub fn with_examples<P: AsRef<Path>>(mut self, examples_bin_path: &P) -> Result<Self, Error> {
+        if examples_bin_path.as_ref().exists() {
+            self.examples = read_bin(examples_bin_path)?;
+        }
+
+        Ok(self)
+    }
+
+    /// Loads the Kanjidic characters, when they were generated.
+    pub fn with_kanji<P: AsRef<Path>>(mut self, kanji_bin_path: &P) -> Result<Self, Error> {
+        if kanji_bin_path.as_ref().exists() {
+            let kanjidic = read_bin::<Kanjidic, P>(kanji_bin_path)?;
+            self = self.with_kanjidic(kanjidic);
+        }
+
+        Ok(self)
+    }
+
+    pub fn with_kanjidic(mut self, kanjidic: Kanjidic) -> Self {
+        self.kanji = kanjidic
+            .characters
+            .into_iter()
+            .map(|kanji| (kanji.literal.clone(), kanji))
+            .collect();
+
+        self
+    }
+
+    /// Loads the components of the kanji, when they were generated.
+    pub fn with_radicals<P: AsRef<Path>>(mut self, radicals_bin_path: &P) -> Result<Self, Error> {
+        if radicals_bin_path.as_ref().exists() {
+            self.radicals = read_bin(radicals_bin_path)?;
+        }
+
+        Ok(self)
+    }
+
+    pub fn with_radical_data(mut self, radicals: Radicals) -> Self {
+        self.radicals = radicals;
+        self
+    }
+
+    /// Loads the KanjiVG strokes of the kanji, when they were imported.
+    pub fn with_strokes<P: AsRef<Path>>(mut self, strokes_bin_path: &P) -> Result<Self, Error> {
+        if strokes_bin_path.as_ref().exists() {
+            self.strokes = read_bin(strokes_bin_path)?;
+        }
+
+        Ok(self)
     }
 
     pub fn word(&self, id: &str) -> Option<&Word> {
@@ -46,18 +180,297 @@ impl DictionaryRepository {
             .collect()
     }
 
+    pub fn kanji(&self, literal: &str) -> Option<&kanjidic::Kanji> {
+        self.kanji.get(literal)
+    }
+
+    /// Kanjidic characters of the kanji of the text, in order and without
+    /// repetitions.
+    pub fn kanji_of(&self, text: &str) -> Vec<&kanjidic::Kanji> {
+        let mut kanji = Vec::<&kanjidic::Kanji>::new();
+
+        for c in text.chars().filter(|c| c.is_kanji()) {
+            if let Some(k) = self.kanji(&c.to_string()) {
+                if !kanji.iter().any(|other| other.literal == k.literal) {
+                    kanji.push(k);
+                }
+            }
+        }
+
+        kanji
+    }
+
+    /// SVG paths of the strokes of the kanji, in stroke order.
+    pub fn strokes(&self, literal: &str) -> &[String] {
+        self.strokes.get(literal).map_or(&[], Vec::as_slice)
+    }
+
+    /// Components of the kanji, e.g. 氵 and 木 for 沐.
+    pub fn components(&self, literal: &str) -> Vec<&str> {
+        self.radicals
+            .components
+            .get(literal)
+            .into_iter()
+            .flatten()
+            .map(|radical| radicals::from_radkfile(radical))
+            .collect()
+    }
+
+    /// Whether the radical is a component of any kanji.
+    pub fn is_radical(&self, radical: &str) -> bool {
+        radicals::to_radkfile(radical)
+            .iter()
+            .any(|radical| self.radicals.radicals.contains_key(*radical))
+    }
+
+    /// Kanji containing all the radicals, from the ones with the fewest
+    /// strokes. A radical written in several ways in RADKFILE (e.g. 阝) matches
+    /// the kanji of any of them.
+    pub fn kanji_with_radicals(&self, radicals: &[&str]) -> Vec<&str> {
+        let mut kanji_sets = radicals.iter().map(|radical| {
+            radicals::to_radkfile(radical)
+                .iter()
+                .filter_map(|radical| self.radicals.radicals.get(*radical))
+                .flat_map(|radical| radical.kanji.iter().map(String::as_str))
+                .collect::<HashSet<&str>>()
+        });
+
+        let Some(first) = kanji_sets.next() else {
+            return vec![];
+        };
+
+        let mut kanji = kanji_sets
+            .fold(first, |all: HashSet<&str>, set| &all & &set)
+            .into_iter()
+            .collect::<Vec<&str>>();
+
+        kanji.sort_by_key(|literal| (self.stroke_count(literal), *literal));
+        kanji
+    }
+
+    /// Kanji with the code, from the ones with the fewest strokes.
+    pub fn kanji_with_code(&self, code: &KanjiCode) -> Vec<&kanjidic::Kanji> {
+        let mut kanji = self
+            .kanji
+            .values()
+            .filter(|kanji| kanji.matches(code))
+            .collect::<Vec<&kanjidic::Kanji>>();
+
+        kanji.sort_by_key(|k| (self.stroke_count(&k.literal), &k.literal));
+        kanji
+    }
+
+    /// Kanji of the school grade, from the most frequent one.
+    pub fn kanji_with_grade(&self, grade: u16) -> Vec<&kanjidic::Kanji> {
+        let mut kanji = self
+            .kanji
+            .values()
+            .filter(|kanji| kanji.misc.grade == Some(grade))
+            .collect::<Vec<&kanjidic::Kanji>>();
+
+        kanji.sort_by_key(|k| {
+            (
+                k.misc.frequency.unwrap_or(u16::MAX),
+                self.stroke_count(&k.literal),
+                &k.literal,
+            )
+        });
+        kanji
+    }
+
+    /// Number of strokes of the kanji, the kanji missing from Kanjidic last.
+    fn stroke_count(&self, literal: &str) -> u8 {
+        self.kanji(literal)
+            .and_then(|k| k.misc.stroke_counts.first().copied())
+            .unwrap_or(u8::MAX)
+    }
+
+    pub fn iter_words(&self) -> impl Iterator<Item = &Word> {
+        self.dictionary.values()
+    }
+
+    /// Words written with the text, as kanji or as kana.
+    pub fn words_with_form(&self, text: &str) -> Vec<&Word> {
+        let forms = self.forms.get_or_init(|| {
+            let mut forms = HashMap::<String, Vec<String>>::new();
+
+            for word in self.dictionary.values() {
+                let texts = word.kanji.iter().map(|k| &k.text);
+
+                for text in texts.chain(word.kana.iter().map(|k| &k.text)) {
+                    let ids = forms.entry(text.clone()).or_default();
+
+                    if !ids.contains(&word.id) {
+                        ids.push(word.id.clone());
+                    }
+                }
+            }
+
+            forms
+        });
+
+        let mut words: Vec<&Word> = forms
+            .get(text)
+            .map(|ids| ids.iter().filter_map(|id| self.word(id)).collect())
+            .unwrap_or_default();
+
+        words.sort_by_key(|word| (!word.is_common(), word.id.clone()));
+        words
+    }
+
+    /// Words with a reading of the word, from the most common and frequent
+    /// ones.
+    pub fn homophones(&self, id: &str) -> Vec<&Word> {
+        let Some(word) = self.word(id) else {
+            return vec![];
+        };
+
+        let homophones = word
+            .kana
+            .iter()
+            .flat_map(|kana| {
+                self.words_with_form(&kana.text)
+                    .into_iter()
+                    .filter(|w| w.kana.iter().any(|k| k.text == kana.text))
+            })
+            .map(|w| (w, 1))
+            .collect();
+
+        self.rank_similar(word, homophones)
+    }
+
+    /// Homophones, words sharing a kanji and synonyms of the word, each from
+    /// the most similar, common and frequent ones.
+    pub fn similar(&self, id: &str) -> Option<SimilarWords<'_>> {
+        let word = self.word(id)?;
+
+        let kanji = word
+            .kanji
+            .iter()
+            .flat_map(|k| k.text.chars())
+            .filter(|c| c.is_kanji())
+            .collect::<HashSet<char>>();
+
+        let shared_kanji = self
+            .dictionary
+            .values()
+            .map(|w| {
+                let shared = w
+                    .kanji
+                    .iter()
+                    .flat_map(|k| k.text.chars())
+                    .filter(|c| kanji.contains(c))
+                    .collect::<HashSet<char>>();
+
+                (w, shared.len())
+            })
+            .collect();
+
+        let glosses = gloss_set(word);
+
+        let synonyms = self
+            .dictionary
+            .values()
+            .map(|w| (w, gloss_set(w).intersection(&glosses).count()))
+            .collect();
+
+        Some(SimilarWords {
+            homophones: self.homophones(id),
+            shared_kanji: self.rank_similar(word, shared_kanji),
+            synonyms: self.rank_similar(word, synonyms),
+        })
+    }
+
+    /// Sorts the words from the most similar one, leaving out the word itself
+    /// and the words with no similarity.
+    fn rank_similar<'a>(&'a self, word: &Word, scored: Vec<(&'a Word, usize)>) -> Vec<&'a Word> {
+        let mut scored = scored
+            .into_iter()
+            .filter(|(w, score)| *score > 0 && w.id != word.id)
+            .collect::<Vec<(&Word, usize)>>();
+
+        scored.sort_by_key(|(w, score)| {
+            (
+                Reverse(*score),
+                !w.is_common(),
+                self.frequency_rank(&w.id).unwrap_or(u32::MAX),
+                w.id.clone(),
+            )
+        });
+        scored.dedup_by(|(a, _), (b, _)| a.id == b.id);
+
+        scored.into_iter().map(|(w, _)| w).collect()
+    }
+
+    /// Words the cross-reference points to, from the common ones. A word with
+    /// a reading must be written with the kanji and read with the kana, and
+    /// one with a sense index must have that many senses.
+    pub fn resolve_xref(&self, xref: &Xref) -> Vec<&Word> {
+        let (text, reading, index) = xref.parts();
+
+        self.words_with_form(text)
+            .into_iter()
+            .filter(|word| reading.is_none_or(|r| word.kana.iter().any(|k| k.text == r)))
+            .filter(|word| index.is_none_or(|i| i >= 1 && word.sense.len() as u64 >= i))
+            .collect()
+    }
+
     pub fn tag(&self, abv: &str) -> Option<&String> {
         self.tags.get(abv)
     }
 
-    /// Search for words in the dictionary that match the given query.
+    pub fn examples(&self, id: &str) -> &[Example] {
+        self.examples.get(id).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    pub fn has_frequencies(&self) -> bool {
+        !self.frequencies.is_empty()
+    }
+
+    /// Frequency rank of the word, 1 being the most frequent one.
+    pub fn frequency_rank(&self, id: &str) -> Option<u32> {
+        self.frequencies.get(id).copied()
+    }
+
+    /// Words ranked within the range, from the most frequent one.
+    pub fn frequent_words(&self, ranks: Range<u32>) -> Vec<&Word> {
+        let mut ranked = self
+            .frequencies
+            .iter()
+            .filter(|(_, rank)| ranks.contains(rank))
+            .filter_map(|(id, rank)| Some((*rank, self.dictionary.get(id)?)))
+            .collect::<Vec<(u32, &Word)>>();
+
+        ranked.sort_by(|(a_rank, a), (b_rank, b)| a_rank.cmp(b_rank).then(a.id.cmp(&b.id)));
+        ranked.into_iter().map(|(_, word)| word).collect()
+    }
+
+    /// Search for words in the dictionary whose kanji contain the query or
+    /// whose reading is the query, regardless of the kana script, the width
+    /// and the long vowels.
     ///
-    /// Returns the `common` kanjis.
+    /// Returns the `common` kanjis, from the most frequent word.
     pub fn search(&self, query: &str, common: Option<bool>) -> Vec<Word> {
-        self.dictionary
-            .iter()
-            .filter(|(id, word)| word.kanji.iter().any(|k| k.text.contains(query)))
-            .map(|(id, word)| {
+        let index = self.search_index.get_or_init(|| self.build_search_index());
+        let query = query.normalize();
+
+        // the forms containing the query contain its first character
+        let kanji_forms: Box<dyn Iterator<Item = &String>> = match query.chars().next() {
+            Some(c) => Box::new(index.kanji_chars.get(&c).into_iter().flatten()),
+            None => Box::new(index.kanji.keys()),
+        };
+
+        let ids = kanji_forms
+            .filter(|form| form.contains(&query))
+            .filter_map(|form| index.kanji.get(form))
+            .chain(index.kana.get(&query))
+            .flatten()
+            .collect::<HashSet<&String>>();
+
+        let mut results = ids
+            .into_iter()
+            .filter_map(|id| self.dictionary.get(id))
+            .map(|word| {
                 if let Some(common) = common {
                     let mut common_word = word.clone();
 
@@ -73,7 +486,48 @@ impl DictionaryRepository {
                     word.clone()
                 }
             })
-            .collect::<Vec<Word>>()
+            .collect::<Vec<Word>>();
+
+        // the words without rank are the least frequent ones
+        results.sort_by_key(|word| {
+            (
+                self.frequency_rank(&word.id).unwrap_or(u32::MAX),
+                word.id.clone(),
+            )
+        });
+        results
+    }
+
+    fn build_search_index(&self) -> SearchIndex {
+        let mut index = SearchIndex::default();
+
+        for word in self.dictionary.values() {
+            for kanji in &word.kanji {
+                let form = kanji.text.normalize();
+
+                if !index.kanji.contains_key(&form) {
+                    let mut chars = form.chars().collect::<Vec<char>>();
+                    chars.sort_unstable();
+                    chars.dedup();
+
+                    for c in chars {
+                        index.kanji_chars.entry(c).or_default().push(form.clone());
+                    }
+                }
+
+                index_form(&mut index.kanji, form, &word.id);
+            }
+
+            for kana in &word.kana {
+                index_form(&mut index.kana, kana.text.normalize(), &word.id);
+            }
+        }
+
+        index
+    }
+
+    pub fn into_words(self) -> Vec<Word> {
+        self.dictionary.into_values().collect()
     }
 
     pub fn num_words(&self) -> usize {
@@ -107,6 +561,35 @@ impl DictionaryRepository {
     }
 }
 
+/// Adds the id of the word to the ones of the form, once.
+fn index_form(index: &mut HashMap<String, Vec<String>>, form: String, id: &String) {
+    let ids = index.entry(form).or_default();
+
+    if !ids.contains(id) {
+        ids.push(id.clone());
+    }
+}
+
+/// Glosses of the word, in lower case.
+fn gloss_set(word: &Word) -> HashSet<String> {
+    word.sense
+        .iter()
+        .flat_map(|sense| &sense.gloss)
+        .map(|gloss| gloss.text.trim().to_lowercase())
+        .collect()
+}
+
+/// Reads the binary data, failing with a hint to run the updater when it was
+/// generated by another version (bincode has no format version to check).
+fn read_bin<T: DeserializeOwned, P: AsRef<Path>>(path: &P) -> Result<T, Error> {
+    bincode::deserialize(&fs::read(path)?).map_err(|_| {
+        Box::new(ErrorKind::Custom(format!(
+            "{} was generated by another version of Nika, run `nika-updater` to update the dictionary",
+            path.as_ref().display()
+        )))
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use std::fs;
@@ -210,6 +693,92 @@ mod tests {
         }
     }
 
+    mod frequency {
+        use super::setup_repo;
+        use crate::importer::frequency::FrequencyMap;
+        use crate::importer::kanjivg::StrokeMap;
+        use crate::importer::tatoeba::Example;
+        use crate::importer::tatoeba::ExampleMap;
+
+        fn setup_ranked_repo() -> super::DictionaryRepository {
+            let mut repo = setup_repo();
+
+            repo.frequencies = FrequencyMap::from([
+                ("1582710".to_string(), 50),
+                ("1318720".to_string(), 800),
+                ("1501350".to_string(), 1200),
+            ]);
+
+            repo
+        }
+
+        #[test]
+        fn test_frequency_rank() {
+            let repo = setup_ranked_repo();
+
+            assert_eq!(repo.frequency_rank("1318720"), Some(800));
+            assert_eq!(repo.frequency_rank("1358280"), None);
+        }
+
+        #[test]
+        fn test_frequent_words() {
+            let repo = setup_ranked_repo();
+
+            let ids = repo
+                .frequent_words(1..1000)
+                .iter()
+                .map(|w| w.id.as_str())
+                .collect::<Vec<&str>>();
+
+            assert_eq!(ids, vec!["1582710", "1318720"]);
+        }
+
+        #[test]
+        fn test_search_normalized_reading() {
+            let repo = setup_repo();
+
+            for query in ["として", "トシテ", "ﾄｼﾃ"] {
+                let ids = repo
+                    .search(query, None)
+                    .into_iter()
+                    .map(|w| w.id)
+                    .collect::<Vec<String>>();
+
+                assert_eq!(ids, vec!["1008590"]);
+            }
+        }
+
+        #[test]
+        fn test_search_kanji_substring() {
+            let repo = setup_repo();
+
+            for query in ["本", "日本"] {
+                let ids = repo
+                    .search(query, None)
+                    .into_iter()
+                    .map(|w| w.id)
+                    .collect::<Vec<String>>();
+
+                assert!(ids.contains(&"1582710".to_string()));
+            }
+
+            assert!(repo.search("本日本", None).is_empty());
+        }
+
+        #[test]
+        fn test_search_by_frequency() {
+            let repo = setup_ranked_repo();
+
+            let ids = repo
+                .search("", None)
+                .into_iter()
+                .map(|w| w.id)
+                .collect::<Vec<String>>();
+
+            assert_eq!(ids, vec!["1582710", "1318720", "1501350", "1358280"]);
+        }
+    }
+
     mod random_words {
         use super::setup_repo;
 
@@ -251,4 +820,263 @@ mod tests {
             assert_eq!(res.len(), total);
         }
     }
+
+    mod xrefs {
+        use test_case::test_case;
+
+        use super::setup_repo;
+        use crate::models::jmdict::Xref;
+
+        fn ids(xref: Xref) -> Vec<String> {
+            let repo = setup_repo();
+
+            repo.resolve_xref(&xref)
+                .iter()
+                .map(|word| word.id.clone())
+                .collect()
+        }
+
+        #[test_case(Xref::Word(("日本".into(),)), &["1582710"] ; "word")]
+        #[test_case(Xref::Word(("たべる".into(),)), &["1358280"] ; "kana")]
+        #[test_case(Xref::WordReading(("日本".into(), "にっぽん".into())), &["1582710"] ; "word reading")]
+        #[test_case(Xref::WordReading(("日本".into(), "たべる".into())), &[] ; "wrong reading")]
+        #[test_case(Xref::WordIndex(("食べる".into(), 2)), &["1358280"] ; "word index")]
+        #[test_case(Xref::WordReadingIndex(("喰べる".into(), "たべる".into(), 3)), &[] ; "missing sense")]
+        #[test_case(Xref::Word(("存在しない".into(),)), &[] ; "missing word")]
+        fn test_resolve_xref(xref: Xref, expected: &[&str]) {
+            assert_eq!(ids(xref), expected);
+        }
+
+        #[test]
+        fn test_resolve_xref_sense_without_gloss() {
+            let mut repo = setup_repo();
+
+            let word = repo.dictionary.get_mut("1358280").unwrap();
+            let target = word.sense[1].clone();
+            for gloss in &mut word.sense[0].gloss {
+                gloss.lang = "ger".into();
+            }
+            word.retain_languages(&["eng".into()]);
+
+            let words = repo.resolve_xref(&Xref::WordIndex(("食べる".into(), 2)));
+
+            assert_eq!(words.len(), 1);
+            assert_eq!(words[0].sense[1], target);
+        }
+    }
+
+    mod similar {
+        use std::fs;
+        use std::path::Path;
+
+        use super::*;
+
+        fn setup_matcher_repo() -> DictionaryRepository {
+            let fixtures_path = Path::new("tests")
+                .join("fixtures")
+                .join("matcher_words.json");
+
+            let words = fs::read_to_string(fixtures_path).unwrap();
+            let data: JMdict = serde_json::from_str(&words).unwrap();
+
+            let words: WordMap = data
+                .words
+                .into_iter()
+                .map(|word| (word.id.clone(), word))
+                .collect();
+
+            DictionaryRepository::from(words, data.tags)
+        }
+
+        fn ids(words: &[&Word]) -> Vec<String> {
+            let mut ids = words.iter().map(|w| w.id.clone()).collect::<Vec<String>>();
+            ids.sort();
+            ids
+        }
+
+        #[test]
+        fn test_similar() {
+            let repo = setup_matcher_repo();
+
+            // 空 (そら)
+            let similar = repo.similar("1383470").unwrap();
+
+            assert_eq!(ids(&similar.homophones), vec!["1006740"]);
+            assert_eq!(
+                ids(&similar.shared_kanji),
+                vec!["1245280", "1382600", "1383480"]
+            );
+            assert_eq!(ids(&similar.synonyms), vec!["1383480"]);
+        }
+
+        #[test]
+        fn test_similar_without_kanji() {
+            let repo = setup_matcher_repo();
+
+            // そら
+            let similar = repo.similar("1006740").unwrap();
+
+            assert_eq!(ids(&similar.homophones), vec!["1383470"]);
+            assert!(similar.shared_kanji.is_empty());
+            assert!(similar.synonyms.is_empty());
+        }
+
+        #[test]
+        fn test_similar_missing_word() {
+            let repo = setup_matcher_repo();
+
+            assert!(repo.similar("9999999").is_none());
+        }
+    }
+
+    mod kanji {
+        use std::fs;
+        use std::path::Path;
+
+        use tempfile::tempdir;
+
+        use super::*;
+
+        fn kanjidic() -> Kanjidic {
+            let fixtures_path = Path::new("tests").join("fixtures").join("kanjidic.json");
+            let content = fs::read_to_string(fixtures_path).unwrap();
+
+            serde_json::from_str(&content).unwrap()
+        }
+
+        #[test]
+        fn test_with_kanji() {
+            let dir = tempdir().unwrap();
+            let path = dir.path().join("kanjidic.bin");
+            fs::write(&path, bincode::serialize(&kanjidic()).unwrap()).unwrap();
+
+            let repo = setup_repo().with_kanji(&path).unwrap();
+
+            let kanji = repo.kanji("館").unwrap();
+            assert_eq!(kanji.misc.stroke_counts, vec![16]);
+            assert_eq!(kanji.on_readings(), vec!["カン"]);
+        }
+
+        #[test]
+        fn test_with_missing_kanji() {
+            let dir = tempdir().unwrap();
+            let repo = setup_repo()
+                .with_kanji(&dir.path().join("kanjidic.bin"))
+                .unwrap();
+
+            assert!(repo.kanji("図").is_none());
+        }
+
+        #[test]
+        fn test_with_outdated_kanji() {
+            let dir = tempdir().unwrap();
+            let path = dir.path().join("kanjidic.bin");
+            fs::write(&path, [1, 2, 3]).unwrap();
+
+            let error = setup_repo().with_kanji(&path).err().unwrap();
+
+            assert!(error.to_string().contains("nika-updater"));
+        }
+
+        #[test]
+        fn test_kanji_with_code() {
+            let repo = setup_repo().with_kanjidic(kanjidic());
+
+            let literals = |code| {
+                repo.kanji_with_code(&code)
+                    .iter()
+                    .map(|k| k.literal.as_str())
+                    .collect::<Vec<&str>>()
+            };
+
+            assert_eq!(literals(KanjiCode::Radical(75)), vec!["本", "林", "森"]);
+            assert_eq!(
+                literals(KanjiCode::FourCorner("3419".into())),
+                vec!["沐", "淋"]
+            );
+            assert_eq!(literals(KanjiCode::Skip("3-3-1".into())), vec!["日"]);
+            assert!(literals(KanjiCode::Skip("4-4-4".into())).is_empty());
+        }
+
+        #[test]
+        fn test_kanji_with_grade() {
+            let repo = setup_repo().with_kanjidic(kanjidic());
+
+            let literals = |grade| {
+                repo.kanji_with_grade(grade)
+                    .iter()
+                    .map(|k| k.literal.as_str())
+                    .collect::<Vec<&str>>()
+            };
+
+            assert_eq!(literals(1), vec!["日", "本", "森", "林"]);
+            assert_eq!(literals(3), vec!["館"]);
+        }
+
+        #[test]
+        fn test_kanji_of() {
+            let repo = setup_repo().with_kanjidic(kanjidic());
+
+            let literals = |text| {
+                repo.kanji_of(text)
+                    .iter()
+                    .map(|k| k.literal.as_str())
+                    .collect::<Vec<&str>>()
+            };
+
+            assert_eq!(literals("図書館"), vec!["図", "書", "館"]);
+            assert_eq!(literals("日本の本"), vec!["日", "本"]);
+            assert!(literals("たべる").is_empty());
+        }
+    }
+
+    mod radicals {
+        use std::fs;
+        use std::path::Path;
+
+        use super::*;
+        use crate::models::radicals::Kradfile;
+        use crate::models::radicals::Radkfile;
+
+        fn fixture<T: serde::de::DeserializeOwned>(name: &str) -> T {
+            let fixtures_path = Path::new("tests").join("fixtures").join(name);
+            let content = fs::read_to_string(fixtures_path).unwrap();
+
+            serde_json::from_str(&content).unwrap()
+        }
+
+        fn setup_radicals_repo() -> DictionaryRepository {
+            let radicals = Radicals::new(
+                fixture::<Kradfile>("kradfile.json"),
+                fixture("radkfile.json"),
+            );
+
+            setup_repo()
+                .with_kanjidic(fixture("kanjidic.json"))
+                .with_radical_data(radicals)
+        }
+
+        #[test]
+        fn test_kanji_with_radicals() {
+            let repo = setup_radicals_repo();
+
+            assert_eq!(repo.kanji_with_radicals(&["氵", "木"]), vec!["沐", "淋"]);
+            assert_eq!(
+                repo.kanji_with_radicals(&["木"]),
+                vec!["本", "沐", "林", "淋", "森"]
+            );
+            assert!(repo.kanji_with_radicals(&["木", "毋"]).is_empty());
+            assert!(repo.kanji_with_radicals(&[]).is_empty());
+        }
+
+        #[test]
+        fn test_components() {
+            let repo = setup_radicals_repo();
+
+            assert_eq!(repo.components("沐"), vec!["氵", "木"]);
+            assert!(repo.components("図").is_empty());
+            assert!(repo.is_radical("氵"));
+            assert!(!repo.is_radical("図"));
+        }
+    }
 }
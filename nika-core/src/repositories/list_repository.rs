@@ -1,3 +1,4 @@
+use std::collections::HashSet;
 use std::fs;
 use std::fs::DirEntry;
 use std::path::PathBuf;
@@ -6,6 +7,7 @@ use std::result;
 use crate::errors::NikaError;
 use crate::errors::Result;
 use crate::errors::StudyListError;
+use crate::models::study_list::ItemStatus;
 use crate::models::study_list::StudyConfig;
 use crate::models::study_list::StudyList;
 
@@ -88,6 +90,45 @@ impl ListRepository {
         )
     }
 
+    /// Items marked with the status in any list.
+    pub fn items_with_status(&self, status: ItemStatus) -> Result<HashSet<String>> {
+        let items = self
+            .get_lists()?
+            .iter()
+            .flat_map(|list| list.items_with_status(status))
+            .map(String::from)
+            .collect();
+
+        Ok(items)
+    }
+
+    /// Sets the status of the item, removing it when the item is new again.
+    pub fn set_item_status(
+        &self,
+        name: &str,
+        item: &str,
+        status: Option<ItemStatus>,
+    ) -> Result<()> {
+        let filepath = self.list_filepath(name);
+
+        if !filepath.exists() {
+            return Err(NikaError::List(StudyListError::ListNotFound));
+        }
+
+        let mut study_list = StudyList::load(&filepath)?;
+
+        if !study_list.items.iter().any(|i| i == item) {
+            return Err(NikaError::List(StudyListError::ItemNotFound));
+        }
+
+        match status {
+            Some(status) => study_list.status.insert(item.to_string(), status),
+            None => study_list.status.remove(item),
+        };
+
+        StudyList::save(&filepath, study_list)
+    }
+
     fn list_filepath(&self, name: &str) -> PathBuf {
         self.dirpath.join(format!("{}.bin", name))
     }
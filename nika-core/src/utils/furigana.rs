@@ -0,0 +1,213 @@
+use super::japanese::JapaneseCharacter;
+use super::japanese::JapaneseString;
+
+/// Part of a written form with the reading of its kanji, if any.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Segment {
+    pub text: String,
+    pub reading: Option<String>,
+}
+
+impl Segment {
+    fn new(text: &str, reading: Option<&str>) -> Self {
+        Self {
+            text: text.to_string(),
+            reading: reading.map(String::from),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    /// Kanji (or other characters) read with one or more kana.
+    Ruby,
+    /// Kana written as they are read, e.g. the okurigana.
+    Kana,
+    /// Punctuation, which may be missing from the reading.
+    Punctuation,
+}
+
+fn kind(c: char) -> Kind {
+    // counters such as ヶ in 一ヶ月 are read as か or こ
+    if matches!(c, 'ヶ' | 'ヵ' | 'ゖ' | 'ゕ') {
+        return Kind::Ruby;
+    }
+
+    if c.is_kana() || c.is_prolonged_sound_mark() {
+        Kind::Kana
+    } else if c.is_japanese_punctuation() {
+        Kind::Punctuation
+    } else {
+        Kind::Ruby
+    }
+}
+
+fn runs(text: &str) -> Vec<(Kind, &str)> {
+    let mut runs = Vec::<(Kind, &str)>::new();
+    let mut start = 0;
+
+    for (i, c) in text.char_indices() {
+        let kind = kind(c);
+
+        match runs.last_mut() {
+            Some((last, run)) if *last == kind => *run = &text[start..i + c.len_utf8()],
+            _ => {
+                start = i;
+                runs.push((kind, &text[i..i + c.len_utf8()]));
+            }
+        }
+    }
+
+    runs
+}
+
+fn same_kana(a: char, b: char) -> bool {
+    a == b || a.is_prolonged_sound_mark() || b.is_prolonged_sound_mark()
+}
+
+/// Positions in the reading where each run ends, trying the shortest reading
+/// of the kanji first.
+fn boundaries(runs: &[(Kind, Vec<char>)], reading: &[char], pos: usize) -> Option<Vec<usize>> {
+    let Some(((kind, chars), rest)) = runs.split_first() else {
+        return (pos == reading.len()).then(Vec::new);
+    };
+
+    let ends: Vec<usize> = match kind {
+        Kind::Ruby if rest.is_empty() => (pos < reading.len())
+            .then_some(reading.len())
+            .into_iter()
+            .collect(),
+        Kind::Ruby => (pos + 1..reading.len()).collect(),
+        Kind::Kana => {
+            let end = pos + chars.len();
+            let found = end <= reading.len()
+                && chars
+                    .iter()
+                    .zip(&reading[pos..end])
+                    .all(|(&a, &b)| same_kana(a, b));
+
+            found.then_some(end).into_iter().collect()
+        }
+        Kind::Punctuation => {
+            let end = pos + chars.len();
+            let found = end <= reading.len() && reading[pos..end] == chars[..];
+
+            // punctuation is usually left out of the reading
+            found.then_some(end).into_iter().chain([pos]).collect()
+        }
+    };
+
+    ends.into_iter().find_map(|end| {
+        let mut ends = boundaries(rest, reading, end)?;
+        ends.insert(0, end);
+        Some(ends)
+    })
+}
+
+/// Aligns the reading with the kanji of the written form, leaving the
+/// okurigana and the other kana without reading, e.g. 食べる and たべる give
+/// 食[た]べる. The whole reading is given to the whole form when the two can't
+/// be aligned.
+pub fn align(text: &str, reading: &str) -> Vec<Segment> {
+    if text.is_empty() || text.normalize() == reading.normalize() {
+        return vec![Segment::new(text, None)];
+    }
+
+    let runs = runs(text);
+    let hiragana_runs = runs
+        .iter()
+        .map(|(kind, run)| (*kind, run.to_hiragana().chars().collect()))
+        .collect::<Vec<(Kind, Vec<char>)>>();
+
+    let reading_chars = reading.chars().collect::<Vec<char>>();
+    let hiragana = reading.to_hiragana().chars().collect::<Vec<char>>();
+
+    let Some(ends) = boundaries(&hiragana_runs, &hiragana, 0) else {
+        return vec![Segment::new(text, Some(reading))];
+    };
+
+    let mut segments = Vec::<Segment>::new();
+    let mut start = 0;
+
+    for ((kind, run), end) in runs.iter().zip(ends) {
+        let segment_reading = reading_chars[start..end].iter().collect::<String>();
+        start = end;
+
+        match (kind, segments.last_mut()) {
+            (Kind::Ruby, _) => segments.push(Segment::new(run, Some(&segment_reading))),
+            // the kana and the punctuation left out of the reading
+            (_, Some(last)) if last.reading.is_none() => last.text.push_str(run),
+            _ => segments.push(Segment::new(run, None)),
+        }
+    }
+
+    segments
+}
+
+/// Formats the segments as Anki furigana, e.g. 食[た]べる. A space separates
+/// each kanji from the text before it, so that the reading applies only to
+/// the kanji.
+pub fn to_anki(segments: &[Segment]) -> String {
+    segments
+        .iter()
+        .enumerate()
+        .map(|(i, segment)| match &segment.reading {
+            Some(reading) if i > 0 => format!(" {}[{}]", segment.text, reading),
+            Some(reading) => format!("{}[{}]", segment.text, reading),
+            None => segment.text.clone(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    fn segments(segments: &[(&str, Option<&str>)]) -> Vec<Segment> {
+        segments
+            .iter()
+            .map(|(text, reading)| Segment::new(text, *reading))
+            .collect()
+    }
+
+    #[test_case("食べる", "たべる", &[("食", Some("た")), ("べる", None)] ; "okurigana")]
+    #[test_case("漢字", "かんじ", &[("漢字", Some("かんじ"))] ; "kanji only")]
+    #[test_case("お茶", "おちゃ", &[("お", None), ("茶", Some("ちゃ"))] ; "prefix")]
+    #[test_case("書き方", "かきかた", &[("書", Some("か")), ("き", None), ("方", Some("かた"))] ; "kana between kanji")]
+    #[test_case("大人しい", "おとなしい", &[("大人", Some("おとな")), ("しい", None)] ; "kanji run")]
+    #[test_case("ソ連", "それん", &[("ソ", None), ("連", Some("れん"))] ; "katakana")]
+    #[test_case("一ヶ月", "いっかげつ", &[("一ヶ月", Some("いっかげつ"))] ; "counter")]
+    #[test_case("として", "として", &[("として", None)] ; "kana only")]
+    fn test_align(text: &str, reading: &str, expected: &[(&str, Option<&str>)]) {
+        assert_eq!(align(text, reading), segments(expected));
+    }
+
+    #[test]
+    fn test_align_mismatch() {
+        assert_eq!(
+            align("食べる", "たべた"),
+            segments(&[("食べる", Some("たべた"))])
+        );
+    }
+
+    #[test]
+    fn test_align_punctuation() {
+        assert_eq!(
+            align("ダイヤル・イン", "ダイヤルイン"),
+            segments(&[("ダイヤル・イン", None)])
+        );
+        assert_eq!(
+            align("お・茶", "おちゃ"),
+            segments(&[("お・", None), ("茶", Some("ちゃ"))])
+        );
+    }
+
+    #[test]
+    fn test_to_anki() {
+        assert_eq!(to_anki(&align("食べる", "たべる")), "食[た]べる");
+        assert_eq!(to_anki(&align("お茶", "おちゃ")), "お 茶[ちゃ]");
+        assert_eq!(to_anki(&align("書き方", "かきかた")), "書[か]き 方[かた]");
+    }
+}
@@ -1,21 +1,545 @@
+/// Writing system of a character.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Script {
+    Kanji,
+    Hiragana,
+    Katakana,
+    /// The prolonged sound mark (ー), written after both kinds of kana.
+    ProlongedSoundMark,
+    Punctuation,
+    Other,
+}
+
 pub trait JapaneseCharacter {
     fn is_kanji(&self) -> bool;
+    fn is_hiragana(&self) -> bool;
+    fn is_katakana(&self) -> bool;
+    fn is_half_width_katakana(&self) -> bool;
+    fn is_small_kana(&self) -> bool;
+    fn is_prolonged_sound_mark(&self) -> bool;
+    fn is_japanese_punctuation(&self) -> bool;
+    fn script(&self) -> Script;
+
+    fn is_kana(&self) -> bool {
+        self.is_hiragana() || self.is_katakana() || self.is_prolonged_sound_mark()
+    }
 }
 
 impl JapaneseCharacter for char {
+    /// CJK ideographs, including the extensions, the compatibility ideographs
+    /// and the iteration marks (e.g. 々).
     fn is_kanji(&self) -> bool {
-        ('\u{4e00}'..='\u{9faf}').contains(self)
+        matches!(
+            self,
+            '\u{4e00}'..='\u{9fff}' // CJK Unified Ideographs
+                | '\u{3400}'..='\u{4dbf}' // Extension A
+                | '\u{20000}'..='\u{2a6df}' // Extension B
+                | '\u{2a700}'..='\u{2ebef}' // Extensions C to F
+                | '\u{30000}'..='\u{323af}' // Extensions G and H
+                | '\u{f900}'..='\u{faff}' // Compatibility Ideographs
+                | '\u{2f800}'..='\u{2fa1f}' // Compatibility Ideographs Supplement
+                | '々' // ideographic iteration mark
+                | '〆' // ideographic closing mark
+                | '〇' // ideographic number zero
+                | '〻' // vertical ideographic iteration mark
+        )
+    }
+
+    /// Hiragana, including the small kana and the iteration marks (e.g. ゝ).
+    fn is_hiragana(&self) -> bool {
+        matches!(self, '\u{3041}'..='\u{3096}' | '\u{309d}'..='\u{309f}')
+    }
+
+    /// Katakana, including the small kana, the iteration marks (e.g. ヽ) and
+    /// the half-width forms.
+    fn is_katakana(&self) -> bool {
+        matches!(
+            self,
+            '\u{30a1}'..='\u{30fa}'
+                | '\u{30fd}'..='\u{30ff}'
+                | '\u{31f0}'..='\u{31ff}' // Katakana Phonetic Extensions
+        ) || (self.is_half_width_katakana() && !self.is_prolonged_sound_mark())
+    }
+
+    fn is_half_width_katakana(&self) -> bool {
+        matches!(self, '\u{ff66}'..='\u{ff9f}')
+    }
+
+    fn is_small_kana(&self) -> bool {
+        matches!(
+            self,
+            'ぁ' | 'ぃ'
+                | 'ぅ'
+                | 'ぇ'
+                | 'ぉ'
+                | 'っ'
+                | 'ゃ'
+                | 'ゅ'
+                | 'ょ'
+                | 'ゎ'
+                | 'ゕ'
+                | 'ゖ'
+                | 'ァ'
+                | 'ィ'
+                | 'ゥ'
+                | 'ェ'
+                | 'ォ'
+                | 'ッ'
+                | 'ャ'
+                | 'ュ'
+                | 'ョ'
+                | 'ヮ'
+                | 'ヵ'
+                | 'ヶ'
+                | '\u{31f0}'..='\u{31ff}'
+                | '\u{ff67}'..='\u{ff6f}'
+        )
+    }
+
+    fn is_prolonged_sound_mark(&self) -> bool {
+        matches!(self, 'ー' | 'ｰ')
+    }
+
+    /// Japanese and full-width punctuation (e.g. 。, 「, ・ and ！).
+    fn is_japanese_punctuation(&self) -> bool {
+        // the full-width forms are shifted from the ASCII ones
+        let full_width_ascii = ('\u{ff01}'..='\u{ff5e}').contains(self)
+            && char::from_u32(*self as u32 - 0xfee0).is_some_and(|c| c.is_ascii_punctuation());
+
+        full_width_ascii
+            || (matches!(
+                self,
+                '\u{3000}'..='\u{303f}' // CJK Symbols and Punctuation
+                    | '\u{309b}'..='\u{309c}' // voiced sound marks
+                    | '・'
+                    | '\u{ff5f}'..='\u{ff65}' // half-width punctuation
+            ) && !self.is_kanji())
+    }
+
+    fn script(&self) -> Script {
+        if self.is_kanji() {
+            Script::Kanji
+        } else if self.is_hiragana() {
+            Script::Hiragana
+        } else if self.is_katakana() {
+            Script::Katakana
+        } else if self.is_prolonged_sound_mark() {
+            Script::ProlongedSoundMark
+        } else if self.is_japanese_punctuation() {
+            Script::Punctuation
+        } else {
+            Script::Other
+        }
     }
 }
 
 pub trait JapaneseString {
     fn has_kanji(&self) -> bool;
+    /// Whether the text is made of kana only, including the prolonged sound
+    /// mark.
+    fn is_kana_only(&self) -> bool;
+    /// Splits the text into the runs of characters of the same script. The
+    /// prolonged sound mark belongs to the kana before it.
+    fn script_runs(&self) -> Vec<(Script, &str)>;
+    /// Converts the katakana to hiragana, keeping the ones without a
+    /// hiragana form (e.g. ヷ).
+    fn to_hiragana(&self) -> String;
+    fn to_katakana(&self) -> String;
+    /// Converts the half-width katakana and punctuation to full-width, and
+    /// the full-width latin letters, digits and symbols to ASCII.
+    fn fold_width(&self) -> String;
+    /// Replaces the prolonged sound mark after a kana with its vowel (e.g.
+    /// こーひー to こおひい).
+    fn expand_long_vowels(&self) -> String;
+    /// Key to compare the words regardless of the kana script, the width and
+    /// the long vowels.
+    fn normalize(&self) -> String;
+    /// Transliterates the kana to Hepburn romaji (e.g. がっこう to gakkou and
+    /// コーヒー to kōhī), keeping the other characters.
+    fn to_romaji(&self) -> String;
+    /// Splits the kana into morae, the small kana (except the sokuon and ヶ)
+    /// belonging to the kana before them (e.g. きょう to きょ and う).
+    fn morae(&self) -> Vec<&str>;
 }
 
-impl JapaneseString for &str {
+impl JapaneseString for str {
     fn has_kanji(&self) -> bool {
         self.chars().any(|c| c.is_kanji())
     }
+
+    fn is_kana_only(&self) -> bool {
+        !self.is_empty() && self.chars().all(|c| c.is_kana())
+    }
+
+    fn script_runs(&self) -> Vec<(Script, &str)> {
+        let mut runs = vec![];
+        let mut current: Option<(Script, usize)> = None;
+
+        for (i, c) in self.char_indices() {
+            let script = match (c.script(), current) {
+                (
+                    Script::ProlongedSoundMark,
+                    Some((script @ (Script::Hiragana | Script::Katakana), _)),
+                ) => script,
+                (script, _) => script,
+            };
+
+            match current {
+                Some((current_script, _)) if current_script == script => {}
+                Some((current_script, start)) => {
+                    runs.push((current_script, &self[start..i]));
+                    current = Some((script, i));
+                }
+                None => current = Some((script, i)),
+            }
+        }
+
+        if let Some((script, start)) = current {
+            runs.push((script, &self[start..]));
+        }
+
+        runs
+    }
+
+    fn to_hiragana(&self) -> String {
+        self.chars()
+            .map(|c| match c {
+                'ァ'..='ヶ' | 'ヽ' | 'ヾ' => shift(c, -0x60),
+                c => c,
+            })
+            .collect()
+    }
+
+    fn to_katakana(&self) -> String {
+        self.chars()
+            .map(|c| match c {
+                'ぁ'..='ゖ' | 'ゝ' | 'ゞ' => shift(c, 0x60),
+                c => c,
+            })
+            .collect()
+    }
+
+    fn fold_width(&self) -> String {
+        let mut folded = String::with_capacity(self.len());
+
+        for c in self.chars() {
+            let previous = folded.chars().last();
+
+            match (c, previous.and_then(|p| combine_sound_mark(p, c))) {
+                (_, Some(combined)) => {
+                    folded.pop();
+                    folded.push(combined);
+                }
+                ('\u{ff01}'..='\u{ff5e}', _) => folded.push(shift(c, -0xfee0)),
+                ('\u{3000}', _) => folded.push(' '),
+                ('\u{ff61}'..='\u{ff9f}', _) => folded.push(HALF_WIDTH[c as usize - 0xff61]),
+                _ => folded.push(c),
+            }
+        }
+
+        folded
+    }
+
+    fn expand_long_vowels(&self) -> String {
+        let mut expanded = String::with_capacity(self.len());
+
+        for c in self.chars() {
+            let vowel = expanded
+                .chars()
+                .last()
+                .filter(|_| c.is_prolonged_sound_mark())
+                .and_then(vowel);
+
+            match vowel {
+                Some(vowel) => expanded.push(vowel),
+                None => expanded.push(c),
+            }
+        }
+
+        expanded
+    }
+
+    fn normalize(&self) -> String {
+        self.fold_width().to_hiragana().expand_long_vowels()
+    }
+
+    fn to_romaji(&self) -> String {
+        let kana = self
+            .fold_width()
+            .to_hiragana()
+            .chars()
+            .collect::<Vec<char>>();
+        let mut romaji = String::with_capacity(kana.len() * 2);
+        let mut sokuon = false;
+        let mut i = 0;
+
+        while i < kana.len() {
+            let c = kana[i];
+            let digraph = kana.get(i + 1).and_then(|&small| digraph(c, small));
+
+            let syllable = match (digraph, c) {
+                (Some(syllable), _) => {
+                    i += 1;
+                    syllable
+                }
+                (None, 'っ') => {
+                    sokuon = true;
+                    i += 1;
+                    continue;
+                }
+                (None, 'ん') => {
+                    // e.g. shin'ichi and kon'ya
+                    let next = kana.get(i + 1).and_then(|&next| romaji_syllable(next));
+                    let apostrophe =
+                        next.is_some_and(|s| s.starts_with(['a', 'i', 'u', 'e', 'o', 'y']));
+
+                    if apostrophe {
+                        "n'".into()
+                    } else {
+                        "n".into()
+                    }
+                }
+                (None, c) if c.is_prolonged_sound_mark() => {
+                    if let Some(vowel) = romaji.pop() {
+                        romaji.push(macron(vowel));
+                    }
+
+                    i += 1;
+                    continue;
+                }
+                (None, c) => romaji_syllable(c).map_or(c.to_string(), String::from),
+            };
+
+            if sokuon {
+                // e.g. matcha and gakkou
+                match syllable.chars().next() {
+                    Some('c') => romaji.push('t'),
+                    Some(consonant) if !"aiueon".contains(consonant) => romaji.push(consonant),
+                    _ => {}
+                }
+
+                sokuon = false;
+            }
+
+            romaji.push_str(&syllable);
+            i += 1;
+        }
+
+        romaji
+    }
+
+    fn morae(&self) -> Vec<&str> {
+        let mut morae = Vec::<&str>::new();
+        let mut start = 0;
+
+        for (i, c) in self.char_indices().skip(1) {
+            let own_mora = matches!(c, 'っ' | 'ッ' | 'ｯ' | 'ゕ' | 'ゖ' | 'ヵ' | 'ヶ');
+
+            if !c.is_small_kana() || own_mora {
+                morae.push(&self[start..i]);
+                start = i;
+            }
+        }
+
+        if !self.is_empty() {
+            morae.push(&self[start..]);
+        }
+
+        morae
+    }
+}
+
+fn romaji_syllable(kana: char) -> Option<&'static str> {
+    let syllable = match kana {
+        'あ' | 'ぁ' => "a",
+        'い' | 'ぃ' => "i",
+        'う' | 'ぅ' => "u",
+        'え' | 'ぇ' => "e",
+        'お' | 'ぉ' | 'を' => "o",
+        'か' | 'ゕ' => "ka",
+        'き' => "ki",
+        'く' => "ku",
+        'け' | 'ゖ' => "ke",
+        'こ' => "ko",
+        'さ' => "sa",
+        'し' => "shi",
+        'す' => "su",
+        'せ' => "se",
+        'そ' => "so",
+        'た' => "ta",
+        'ち' => "chi",
+        'つ' => "tsu",
+        'て' => "te",
+        'と' => "to",
+        'な' => "na",
+        'に' => "ni",
+        'ぬ' => "nu",
+        'ね' => "ne",
+        'の' => "no",
+        'は' => "ha",
+        'ひ' => "hi",
+        'ふ' => "fu",
+        'へ' => "he",
+        'ほ' => "ho",
+        'ま' => "ma",
+        'み' => "mi",
+        'む' => "mu",
+        'め' => "me",
+        'も' => "mo",
+        'や' | 'ゃ' => "ya",
+        'ゆ' | 'ゅ' => "yu",
+        'よ' | 'ょ' => "yo",
+        'ら' => "ra",
+        'り' => "ri",
+        'る' => "ru",
+        'れ' => "re",
+        'ろ' => "ro",
+        'わ' | 'ゎ' => "wa",
+        'ゐ' => "i",
+        'ゑ' => "e",
+        'が' => "ga",
+        'ぎ' => "gi",
+        'ぐ' => "gu",
+        'げ' => "ge",
+        'ご' => "go",
+        'ざ' => "za",
+        'じ' | 'ぢ' => "ji",
+        'ず' | 'づ' => "zu",
+        'ぜ' => "ze",
+        'ぞ' => "zo",
+        'だ' => "da",
+        'で' => "de",
+        'ど' => "do",
+        'ば' => "ba",
+        'び' => "bi",
+        'ぶ' => "bu",
+        'べ' => "be",
+        'ぼ' => "bo",
+        'ぱ' => "pa",
+        'ぴ' => "pi",
+        'ぷ' => "pu",
+        'ぺ' => "pe",
+        'ぽ' => "po",
+        'ゔ' => "vu",
+        'ヷ' => "va",
+        'ヸ' => "vi",
+        'ヹ' => "ve",
+        'ヺ' => "vo",
+        _ => return None,
+    };
+
+    Some(syllable)
+}
+
+/// Romaji of a kana followed by a small kana, e.g. きょ or ふぁ.
+fn digraph(kana: char, small: char) -> Option<String> {
+    let consonant = match (kana, small) {
+        ('き', 'ゃ' | 'ゅ' | 'ょ') => "ky",
+        ('ぎ', 'ゃ' | 'ゅ' | 'ょ') => "gy",
+        ('し', 'ゃ' | 'ゅ' | 'ょ' | 'ぇ') => "sh",
+        ('じ' | 'ぢ', 'ゃ' | 'ゅ' | 'ょ' | 'ぇ') => "j",
+        ('ち', 'ゃ' | 'ゅ' | 'ょ' | 'ぇ') => "ch",
+        ('に', 'ゃ' | 'ゅ' | 'ょ') => "ny",
+        ('ひ', 'ゃ' | 'ゅ' | 'ょ') => "hy",
+        ('び', 'ゃ' | 'ゅ' | 'ょ') => "by",
+        ('ぴ', 'ゃ' | 'ゅ' | 'ょ') => "py",
+        ('み', 'ゃ' | 'ゅ' | 'ょ') => "my",
+        ('り', 'ゃ' | 'ゅ' | 'ょ') => "ry",
+        ('ふ', 'ぁ' | 'ぃ' | 'ぇ' | 'ぉ' | 'ゅ') => "f",
+        ('ゔ', 'ぁ' | 'ぃ' | 'ぇ' | 'ぉ') => "v",
+        ('つ', 'ぁ' | 'ぃ' | 'ぇ' | 'ぉ') => "ts",
+        ('て', 'ぃ' | 'ゅ') => "t",
+        ('で', 'ぃ' | 'ゅ') => "d",
+        ('と', 'ぅ') => "t",
+        ('ど', 'ぅ') => "d",
+        ('う', 'ぃ' | 'ぇ' | 'ぉ') => "w",
+        ('い', 'ぇ') => "y",
+        _ => return None,
+    };
+
+    let vowel = match small {
+        'ぁ' | 'ゃ' => 'a',
+        'ぃ' => 'i',
+        'ぅ' | 'ゅ' => 'u',
+        'ぇ' => 'e',
+        _ => 'o',
+    };
+
+    Some(format!("{}{}", consonant, vowel))
+}
+
+fn macron(vowel: char) -> char {
+    match vowel {
+        'a' => 'ā',
+        'i' => 'ī',
+        'u' => 'ū',
+        'e' => 'ē',
+        'o' => 'ō',
+        c => c,
+    }
+}
+
+/// Full-width forms of the half-width characters from U+FF61 to U+FF9F.
+const HALF_WIDTH: [char; 63] = [
+    '。', '「', '」', '、', '・', 'ヲ', 'ァ', 'ィ', 'ゥ', 'ェ', 'ォ', 'ャ', 'ュ', 'ョ', 'ッ', 'ー',
+    'ア', 'イ', 'ウ', 'エ', 'オ', 'カ', 'キ', 'ク', 'ケ', 'コ', 'サ', 'シ', 'ス', 'セ', 'ソ', 'タ',
+    'チ', 'ツ', 'テ', 'ト', 'ナ', 'ニ', 'ヌ', 'ネ', 'ノ', 'ハ', 'ヒ', 'フ', 'ヘ', 'ホ', 'マ', 'ミ',
+    'ム', 'メ', 'モ', 'ヤ', 'ユ', 'ヨ', 'ラ', 'リ', 'ル', 'レ', 'ロ', 'ワ', 'ン', '゛', '゜',
+];
+
+fn shift(c: char, offset: i32) -> char {
+    char::from_u32((c as i32 + offset) as u32).unwrap_or(c)
+}
+
+/// Combines a full-width kana with the following half-width (semi-)voiced
+/// sound mark, e.g. カ and ﾞ to ガ.
+fn combine_sound_mark(kana: char, mark: char) -> Option<char> {
+    match mark {
+        'ﾞ' if kana == 'ウ' => Some('ヴ'),
+        'ﾞ' if matches!(kana, 'カ'..='ト' | 'ハ'..='ホ') && !is_voiced(kana) => {
+            Some(shift(kana, 1))
+        }
+        'ﾟ' if matches!(kana, 'ハ'..='ホ') && (kana as u32 - 'ハ' as u32).is_multiple_of(3) => {
+            Some(shift(kana, 2))
+        }
+        _ => None,
+    }
+}
+
+/// Whether the katakana between カ and ホ has a (semi-)voiced sound mark or
+/// is a small ッ.
+fn is_voiced(kana: char) -> bool {
+    match kana {
+        'カ'..='チ' => !(kana as u32 - 'カ' as u32).is_multiple_of(2),
+        'ッ'..='ト' => (kana as u32 - 'ッ' as u32).is_multiple_of(2),
+        'ハ'..='ホ' => !(kana as u32 - 'ハ' as u32).is_multiple_of(3),
+        _ => false,
+    }
+}
+
+/// Vowel of a kana, in the same script.
+fn vowel(kana: char) -> Option<char> {
+    let hiragana = kana.to_string().to_hiragana();
+
+    let vowel = match hiragana.chars().next()? {
+        'あ' | 'か' | 'が' | 'さ' | 'ざ' | 'た' | 'だ' | 'な' | 'は' | 'ば' | 'ぱ' | 'ま'
+        | 'や' | 'ら' | 'わ' | 'ぁ' | 'ゃ' | 'ゎ' | 'ゕ' => 'あ',
+        'い' | 'き' | 'ぎ' | 'し' | 'じ' | 'ち' | 'ぢ' | 'に' | 'ひ' | 'び' | 'ぴ' | 'み'
+        | 'り' | 'ぃ' => 'い',
+        'う' | 'く' | 'ぐ' | 'す' | 'ず' | 'つ' | 'づ' | 'ぬ' | 'ふ' | 'ぶ' | 'ぷ' | 'む'
+        | 'ゆ' | 'る' | 'ぅ' | 'ゅ' | 'ゔ' => 'う',
+        'え' | 'け' | 'げ' | 'せ' | 'ぜ' | 'て' | 'で' | 'ね' | 'へ' | 'べ' | 'ぺ' | 'め'
+        | 'れ' | 'ぇ' | 'ゖ' => 'え',
+        'お' | 'こ' | 'ご' | 'そ' | 'ぞ' | 'と' | 'ど' | 'の' | 'ほ' | 'ぼ' | 'ぽ' | 'も'
+        | 'よ' | 'ろ' | 'を' | 'ぉ' | 'ょ' => 'お',
+        _ => return None,
+    };
+
+    if kana.is_katakana() {
+        Some(shift(vowel, 0x60))
+    } else {
+        Some(vowel)
+    }
 }
 
 #[cfg(test)]
@@ -24,18 +548,146 @@ mod tests {
 
     use super::*;
 
-    #[test_case('日', true)]
-    #[test_case('ひ', false)]
-    #[test_case('ミ', false)]
-    #[test_case('S', false)]
+    #[test_case('日', true ; "unified")]
+    #[test_case('㐀', true ; "extension a")]
+    #[test_case('𠀋', true ; "extension b")]
+    #[test_case('﨑', true ; "compatibility")]
+    #[test_case('々', true ; "iteration mark")]
+    #[test_case('ひ', false ; "hiragana")]
+    #[test_case('ミ', false ; "katakana")]
+    #[test_case('。', false ; "punctuation")]
+    #[test_case('S', false ; "latin")]
     fn test_kanji_caracter(character: char, expected: bool) {
         assert_eq!(character.is_kanji(), expected);
     }
 
+    #[test_case('ひ', Script::Hiragana ; "hiragana")]
+    #[test_case('ゝ', Script::Hiragana ; "hiragana iteration mark")]
+    #[test_case('ゃ', Script::Hiragana ; "small hiragana")]
+    #[test_case('ミ', Script::Katakana ; "katakana")]
+    #[test_case('ヶ', Script::Katakana ; "small katakana")]
+    #[test_case('ㇰ', Script::Katakana ; "katakana extension")]
+    #[test_case('ｶ', Script::Katakana ; "half width katakana")]
+    #[test_case('ー', Script::ProlongedSoundMark ; "prolonged sound mark")]
+    #[test_case('ｰ', Script::ProlongedSoundMark ; "half width prolonged sound mark")]
+    #[test_case('。', Script::Punctuation ; "full stop")]
+    #[test_case('「', Script::Punctuation ; "quotation mark")]
+    #[test_case('・', Script::Punctuation ; "middle dot")]
+    #[test_case('！', Script::Punctuation ; "full width punctuation")]
+    #[test_case('｡', Script::Punctuation ; "half width punctuation")]
+    #[test_case('Ａ', Script::Other ; "full width latin")]
+    #[test_case('a', Script::Other ; "latin")]
+    fn test_script(character: char, expected: Script) {
+        assert_eq!(character.script(), expected);
+    }
+
+    #[test_case('っ', true ; "small hiragana")]
+    #[test_case('ョ', true ; "small katakana")]
+    #[test_case('ｯ', true ; "small half width katakana")]
+    #[test_case('つ', false ; "hiragana")]
+    #[test_case('ﾂ', false ; "half width katakana")]
+    fn test_small_kana(character: char, expected: bool) {
+        assert_eq!(character.is_small_kana(), expected);
+    }
+
     #[test_case("日本語", true)]
+    #[test_case("人々", true)]
     #[test_case("This 文字 contains kanji", true)]
     #[test_case("This ストリング does not contain kanji", false)]
     fn test_kanji_string(text: &str, expected: bool) {
         assert_eq!(text.has_kanji(), expected);
     }
+
+    #[test_case("ひらがな", true ; "hiragana")]
+    #[test_case("コーヒー", true ; "katakana")]
+    #[test_case("ｺｰﾋｰ", true ; "half width katakana")]
+    #[test_case("食べる", false ; "kanji")]
+    #[test_case("ひらがな。", false ; "punctuation")]
+    #[test_case("", false ; "empty")]
+    fn test_kana_only(text: &str, expected: bool) {
+        assert_eq!(text.is_kana_only(), expected);
+    }
+
+    #[test]
+    fn test_script_runs() {
+        assert_eq!(
+            "食べるコーヒー、ABC".script_runs(),
+            vec![
+                (Script::Kanji, "食"),
+                (Script::Hiragana, "べる"),
+                (Script::Katakana, "コーヒー"),
+                (Script::Punctuation, "、"),
+                (Script::Other, "ABC"),
+            ]
+        );
+        assert_eq!(
+            "ーあ".script_runs(),
+            vec![(Script::ProlongedSoundMark, "ー"), (Script::Hiragana, "あ")]
+        );
+        assert!("".script_runs().is_empty());
+    }
+
+    #[test]
+    fn test_kana_conversion() {
+        assert_eq!(
+            "コーヒーとヴァイオリン".to_hiragana(),
+            "こーひーとゔぁいおりん"
+        );
+        assert_eq!("ひらがなゝ、ABC".to_katakana(), "ヒラガナヽ、ABC");
+        assert_eq!("ヷ".to_hiragana(), "ヷ");
+    }
+
+    #[test_case("ｺｰﾋｰ", "コーヒー" ; "half width katakana")]
+    #[test_case("ｶﾞｯｺｳ", "ガッコウ" ; "voiced sound mark")]
+    #[test_case("ﾊﾟﾋﾞｳﾞ", "パビヴ" ; "semi voiced sound mark")]
+    #[test_case("ﾂﾟ", "ツ゜" ; "invalid sound mark")]
+    #[test_case("｢ﾃｽﾄ｣｡", "「テスト」。" ; "half width punctuation")]
+    #[test_case("ＡＢＣ１２３！", "ABC123!" ; "full width latin")]
+    fn test_fold_width(text: &str, expected: &str) {
+        assert_eq!(text.fold_width(), expected);
+    }
+
+    #[test_case("こーひー", "こおひい" ; "hiragana")]
+    #[test_case("コーヒー", "コオヒイ" ; "katakana")]
+    #[test_case("ラーメン", "ラアメン" ; "a vowel")]
+    #[test_case("ーあ", "ーあ" ; "without kana")]
+    #[test_case("んー", "んー" ; "without vowel")]
+    fn test_expand_long_vowels(text: &str, expected: &str) {
+        assert_eq!(text.expand_long_vowels(), expected);
+    }
+
+    #[test]
+    fn test_normalize() {
+        let key = "こおひい";
+
+        assert_eq!("コーヒー".normalize(), key);
+        assert_eq!("こーひー".normalize(), key);
+        assert_eq!("ｺｰﾋｰ".normalize(), key);
+        assert_eq!("食べる".normalize(), "食べる");
+    }
+
+    #[test_case("たべる", "taberu" ; "hiragana")]
+    #[test_case("しんぶん", "shinbun" ; "n")]
+    #[test_case("きょうと", "kyouto" ; "digraph")]
+    #[test_case("がっこう", "gakkou" ; "sokuon")]
+    #[test_case("まっちゃ", "matcha" ; "sokuon before ch")]
+    #[test_case("ちょっと", "chotto" ; "sokuon after digraph")]
+    #[test_case("しんいち", "shin'ichi" ; "n before vowel")]
+    #[test_case("こんや", "kon'ya" ; "n before y")]
+    #[test_case("コーヒー", "kōhī" ; "prolonged sound mark")]
+    #[test_case("ファイル", "fairu" ; "extended katakana")]
+    #[test_case("ﾊﾟｰﾃｨｰ", "pātī" ; "half width")]
+    #[test_case("日本へ", "日本he" ; "kanji")]
+    fn test_to_romaji(text: &str, expected: &str) {
+        assert_eq!(text.to_romaji(), expected);
+    }
+
+    #[test_case("きょう", vec!["きょ", "う"] ; "digraph")]
+    #[test_case("がっこう", vec!["が", "っ", "こ", "う"] ; "sokuon")]
+    #[test_case("ファイル", vec!["ファ", "イ", "ル"] ; "extended katakana")]
+    #[test_case("ラーメン", vec!["ラ", "ー", "メ", "ン"] ; "prolonged sound mark")]
+    #[test_case("", vec![] ; "empty")]
+    fn test_morae(text: &str, expected: Vec<&str>) {
+        assert_eq!(text.morae(), expected);
+    }
 }
@@ -1 +1,3 @@
+pub mod furigana;
 pub mod japanese;
+pub mod strokes;
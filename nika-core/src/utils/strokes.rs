@@ -0,0 +1,391 @@
+use crate::importer::kanjivg::KANJIVG_SIZE;
+
+/// Segments drawn for each Bézier curve.
+const CURVE_SEGMENTS: usize = 8;
+
+pub type Point = (f32, f32);
+
+/// Points along the SVG path, following its lines and curves. Only the
+/// commands of the KanjiVG paths are supported (`M`, `L`, `H`, `V`, `C`, `S`
+/// and `Z`), the path stopping at any other one.
+pub fn flatten_path(path: &str) -> Vec<Point> {
+    let tokens = tokenize(path);
+    let mut points = Vec::<Point>::new();
+
+    let mut current = (0.0, 0.0);
+    let mut start = (0.0, 0.0);
+    // second control point of the last curve, reflected by `S`
+    let mut last_control: Option<Point> = None;
+    let mut command = 'M';
+    let mut i = 0;
+
+    let number = |i: usize| match tokens.get(i) {
+        Some(Token::Number(n)) => Some(*n),
+        _ => None,
+    };
+
+    while i < tokens.len() {
+        if let Token::Command(c) = tokens[i] {
+            command = c;
+            i += 1;
+
+            if c.eq_ignore_ascii_case(&'z') {
+                current = start;
+                points.push(current);
+                last_control = None;
+                continue;
+            }
+        }
+
+        let relative = command.is_ascii_lowercase();
+        let offset = |(x, y): Point| match relative {
+            true => (current.0 + x, current.1 + y),
+            false => (x, y),
+        };
+
+        let arity = match command.to_ascii_uppercase() {
+            'M' | 'L' => 2,
+            'H' | 'V' => 1,
+            'C' => 6,
+            'S' => 4,
+            _ => break,
+        };
+
+        let Some(values) = (i..i + arity).map(number).collect::<Option<Vec<f32>>>() else {
+            break;
+        };
+
+        i += arity;
+
+        match command.to_ascii_uppercase() {
+            'M' => {
+                current = offset((values[0], values[1]));
+                start = current;
+                points.push(current);
+                last_control = None;
+                // the next pairs of coordinates are lines
+                command = if relative { 'l' } else { 'L' };
+            }
+            'L' => {
+                current = offset((values[0], values[1]));
+                points.push(current);
+                last_control = None;
+            }
+            'H' => {
+                current.0 = if relative {
+                    current.0 + values[0]
+                } else {
+                    values[0]
+                };
+                points.push(current);
+                last_control = None;
+            }
+            'V' => {
+                current.1 = if relative {
+                    current.1 + values[0]
+                } else {
+                    values[0]
+                };
+                points.push(current);
+                last_control = None;
+            }
+            'C' | 'S' => {
+                let (control1, control2, end) = if command.eq_ignore_ascii_case(&'C') {
+                    (
+                        offset((values[0], values[1])),
+                        offset((values[2], values[3])),
+                        offset((values[4], values[5])),
+                    )
+                } else {
+                    let control1 = last_control
+                        .map_or(current, |(x, y)| (2.0 * current.0 - x, 2.0 * current.1 - y));
+
+                    (
+                        control1,
+                        offset((values[0], values[1])),
+                        offset((values[2], values[3])),
+                    )
+                };
+
+                for step in 1..=CURVE_SEGMENTS {
+                    let t = step as f32 / CURVE_SEGMENTS as f32;
+                    points.push(cubic(current, control1, control2, end, t));
+                }
+
+                current = end;
+                last_control = Some(control2);
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    points
+}
+
+fn cubic(p0: Point, p1: Point, p2: Point, p3: Point, t: f32) -> Point {
+    let u = 1.0 - t;
+    let a = u * u * u;
+    let b = 3.0 * u * u * t;
+    let c = 3.0 * u * t * t;
+    let d = t * t * t;
+
+    (
+        a * p0.0 + b * p1.0 + c * p2.0 + d * p3.0,
+        a * p0.1 + b * p1.1 + c * p2.1 + d * p3.1,
+    )
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Token {
+    Command(char),
+    Number(f32),
+}
+
+/// Splits the path into commands and numbers, which may be separated by
+/// commas, spaces or only by their sign or decimal point (e.g. `1.5.5-2`).
+fn tokenize(path: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut number = String::new();
+
+    let flush = |number: &mut String, tokens: &mut Vec<Token>| {
+        if let Ok(n) = number.parse::<f32>() {
+            tokens.push(Token::Number(n));
+        }
+        number.clear();
+    };
+
+    for c in path.chars() {
+        match c {
+            '0'..='9' => number.push(c),
+            '.' if number.contains('.') && !number.ends_with(['e', 'E']) => {
+                flush(&mut number, &mut tokens);
+                number.push(c);
+            }
+            '.' => number.push(c),
+            '-' | '+' if number.ends_with(['e', 'E']) => number.push(c),
+            '-' | '+' => {
+                flush(&mut number, &mut tokens);
+                number.push(c);
+            }
+            'e' | 'E' if !number.is_empty() => number.push(c),
+            c if c.is_ascii_alphabetic() => {
+                flush(&mut number, &mut tokens);
+                tokens.push(Token::Command(c));
+            }
+            _ => flush(&mut number, &mut tokens),
+        }
+    }
+
+    flush(&mut number, &mut tokens);
+
+    tokens
+}
+
+/// Grid of dots drawn with the Unicode braille characters, of 2 by 4 dots.
+pub struct BrailleCanvas {
+    columns: usize,
+    rows: usize,
+    dots: Vec<bool>,
+}
+
+impl BrailleCanvas {
+    pub fn new(columns: usize, rows: usize) -> Self {
+        Self {
+            columns,
+            rows,
+            dots: vec![false; columns * 2 * rows * 4],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.columns * 2
+    }
+
+    pub fn height(&self) -> usize {
+        self.rows * 4
+    }
+
+    pub fn set(&mut self, x: usize, y: usize) {
+        if x < self.width() && y < self.height() {
+            let width = self.width();
+            self.dots[y * width + x] = true;
+        }
+    }
+
+    /// Draws the points joined by lines, scaled from a square drawing of the
+    /// size to the canvas.
+    pub fn draw_polyline(&mut self, points: &[Point], size: f32) {
+        let scale_x = (self.width() - 1) as f32 / size;
+        let scale_y = (self.height() - 1) as f32 / size;
+
+        let scaled = points
+            .iter()
+            .map(|(x, y)| (x * scale_x, y * scale_y))
+            .collect::<Vec<Point>>();
+
+        if let [point] = scaled[..] {
+            self.set(point.0.round() as usize, point.1.round() as usize);
+        }
+
+        for line in scaled.windows(2) {
+            let ((x0, y0), (x1, y1)) = (line[0], line[1]);
+            let steps = (x1 - x0).abs().max((y1 - y0).abs()).ceil().max(1.0) as usize;
+
+            for step in 0..=steps {
+                let t = step as f32 / steps as f32;
+                let x = x0 + (x1 - x0) * t;
+                let y = y0 + (y1 - y0) * t;
+
+                self.set(x.max(0.0).round() as usize, y.max(0.0).round() as usize);
+            }
+        }
+    }
+
+    /// Lines of braille characters of the canvas.
+    pub fn lines(&self) -> Vec<String> {
+        // bits of the dots of a character, by column and row
+        const BITS: [[u32; 4]; 2] = [[0x01, 0x02, 0x04, 0x40], [0x08, 0x10, 0x20, 0x80]];
+
+        (0..self.rows)
+            .map(|row| {
+                (0..self.columns)
+                    .map(|column| {
+                        let mut code = 0x2800;
+
+                        for (dx, bits) in BITS.iter().enumerate() {
+                            for (dy, bit) in bits.iter().enumerate() {
+                                let x = column * 2 + dx;
+                                let y = row * 4 + dy;
+
+                                if self.dots[y * self.width() + x] {
+                                    code |= bit;
+                                }
+                            }
+                        }
+
+                        char::from_u32(code).unwrap_or(' ')
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// SVG worksheet of the stroke order, with a frame for each stroke showing
+/// the previous strokes in gray and the stroke in black, numbered at its
+/// start.
+pub fn stroke_order_svg(paths: &[String], columns: usize) -> String {
+    let columns = columns.max(1);
+    let rows = paths.len().div_ceil(columns).max(1);
+    let size = KANJIVG_SIZE as usize;
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\">\n",
+        columns * size,
+        rows * size,
+        columns * size,
+        rows * size
+    );
+
+    for (i, path) in paths.iter().enumerate() {
+        let x = (i % columns) * size;
+        let y = (i / columns) * size;
+
+        svg.push_str(&format!(
+            "<g transform=\"translate({} {})\">\n<rect width=\"{}\" height=\"{}\" style=\"fill:none;stroke:#ddd;stroke-width:0.5\"/>\n",
+            x, y, size, size
+        ));
+
+        for previous in &paths[..i] {
+            svg.push_str(&format!(
+                "<path d=\"{}\" style=\"fill:none;stroke:#bbb;stroke-width:3;stroke-linecap:round\"/>\n",
+                previous
+            ));
+        }
+
+        svg.push_str(&format!(
+            "<path d=\"{}\" style=\"fill:none;stroke:#000;stroke-width:3;stroke-linecap:round\"/>\n",
+            path
+        ));
+
+        if let Some((start_x, start_y)) = flatten_path(path).first() {
+            svg.push_str(&format!(
+                "<text x=\"{:.1}\" y=\"{:.1}\" style=\"font-size:8px;fill:#c00\">{}</text>\n",
+                start_x - 6.0,
+                start_y - 2.0,
+                i + 1
+            ));
+        }
+
+        svg.push_str("</g>\n");
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    #[test_case("M1,2L3,4", vec![Token::Command('M'), Token::Number(1.0), Token::Number(2.0), Token::Command('L'), Token::Number(3.0), Token::Number(4.0)] ; "commas")]
+    #[test_case("c1.5.5-2,0", vec![Token::Command('c'), Token::Number(1.5), Token::Number(0.5), Token::Number(-2.0), Token::Number(0.0)] ; "packed")]
+    #[test_case("m1e-1 2", vec![Token::Command('m'), Token::Number(0.1), Token::Number(2.0)] ; "exponent")]
+    fn test_tokenize(path: &str, expected: Vec<Token>) {
+        assert_eq!(tokenize(path), expected);
+    }
+
+    #[test]
+    fn test_flatten_lines() {
+        assert_eq!(
+            flatten_path("M10,10l5,0 0,5H0v-5z"),
+            vec![
+                (10.0, 10.0),
+                (15.0, 10.0),
+                (15.0, 15.0),
+                (0.0, 15.0),
+                (0.0, 10.0),
+                (10.0, 10.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_flatten_curves() {
+        let points = flatten_path("M0,0c0,10,10,10,10,0s10,-10,10,0");
+
+        assert_eq!(points.len(), 1 + 2 * CURVE_SEGMENTS);
+        assert_eq!(points[CURVE_SEGMENTS], (10.0, 0.0));
+        assert_eq!(points[2 * CURVE_SEGMENTS], (20.0, 0.0));
+        // the reflected control point bends the second curve upwards
+        assert!(points[CURVE_SEGMENTS + CURVE_SEGMENTS / 2].1 < 0.0);
+    }
+
+    #[test]
+    fn test_stroke_order_svg() {
+        let paths = vec![
+            "M25,28c2,0,4,0,7,0".to_string(),
+            "M14,81c3,1,7,1,11,0".to_string(),
+        ];
+
+        let svg = stroke_order_svg(&paths, 1);
+
+        assert!(svg.contains("width=\"109\" height=\"218\""));
+        assert_eq!(svg.matches("<path").count(), 3);
+        assert!(svg.contains("translate(0 109)"));
+        assert!(svg.contains(">2</text>"));
+    }
+
+    #[test]
+    fn test_braille_canvas() {
+        let mut canvas = BrailleCanvas::new(2, 1);
+        canvas.draw_polyline(&[(0.0, 0.0), (3.0, 0.0)], 3.0);
+
+        assert_eq!(canvas.lines(), vec!["⠉⠉"]);
+
+        canvas.set(0, 3);
+        assert_eq!(canvas.lines(), vec!["⡉⠉"]);
+    }
+}
@@ -1 +1,4 @@
+pub mod test_analysis_controller;
+pub mod test_import_controller;
+pub mod test_kanji_controller;
 pub mod test_study_controller;
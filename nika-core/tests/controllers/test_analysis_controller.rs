@@ -0,0 +1,101 @@
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+
+use nika_core::controllers::analysis_controller::AnalysisController;
+use nika_core::models::jmdict::JMdict;
+use nika_core::models::jmdict::Word;
+use nika_core::models::study_list::ItemStatus;
+use nika_core::models::study_list::StudyList;
+use nika_core::repositories::config_repository::ConfigRepository;
+use nika_core::repositories::dictionary_repository::DictionaryRepository;
+use nika_core::repositories::dictionary_repository::WordMap;
+use nika_core::repositories::list_repository::ListRepository;
+use tempfile::tempdir;
+
+fn setup(lists: Vec<StudyList>) -> AnalysisController {
+    setup_with_config(lists, tempdir().unwrap().into_path().join("config.toml"))
+}
+
+fn setup_with_config(lists: Vec<StudyList>, config_filepath: PathBuf) -> AnalysisController {
+    let lists_path = tempdir().unwrap().into_path();
+    let list_repository = ListRepository::new(lists_path);
+
+    for list in lists {
+        list_repository.add_list(list).unwrap();
+    }
+
+    let words_path = Path::new("tests").join("fixtures").join("words.json");
+    let data: JMdict = serde_json::from_str(&fs::read_to_string(words_path).unwrap()).unwrap();
+
+    let words: WordMap = data
+        .words
+        .into_iter()
+        .map(|word| (word.id.clone(), word))
+        .collect();
+
+    AnalysisController::new(
+        DictionaryRepository::from(words, data.tags),
+        ConfigRepository::new(config_filepath),
+        list_repository,
+    )
+}
+
+fn ids<'a>(unknown: &'a [(&'a Word, usize)]) -> Vec<(&'a str, usize)> {
+    unknown
+        .iter()
+        .map(|(word, count)| (word.id.as_str(), *count))
+        .collect()
+}
+
+const TEXT: &str = "日本は自由だ。\n食べた。日本として食べます。\n日本";
+
+#[test]
+fn test_unknown_words() {
+    let controller = setup(vec![]);
+
+    let unknown = controller.unknown_words(TEXT).unwrap();
+
+    assert_eq!(
+        ids(&unknown),
+        vec![
+            ("1582710", 3),
+            ("1358280", 2),
+            ("1318720", 1),
+            ("1008590", 1)
+        ]
+    );
+}
+
+#[test]
+fn test_unknown_words_without_known() {
+    let mut list = StudyList::new("list", vec!["1582710".into(), "1358280".into()]);
+    list.status.insert("1582710".into(), ItemStatus::Known);
+    list.status.insert("1358280".into(), ItemStatus::Skipped);
+
+    let controller = setup(vec![list]);
+
+    let unknown = controller.unknown_words(TEXT).unwrap();
+
+    assert_eq!(
+        ids(&unknown),
+        vec![("1358280", 2), ("1318720", 1), ("1008590", 1)]
+    );
+}
+
+#[test]
+fn test_create_list_selects_first() {
+    let config_filepath = tempdir().unwrap().into_path().join("config.toml");
+    let config_repository = ConfigRepository::new(config_filepath.clone());
+    let controller = setup_with_config(vec![], config_filepath);
+
+    controller
+        .create_list("unknown", vec!["1358280".into()])
+        .unwrap();
+    controller.create_list("other", vec![]).unwrap();
+
+    assert_eq!(
+        config_repository.get_current_list().unwrap(),
+        Some("unknown".into())
+    );
+}
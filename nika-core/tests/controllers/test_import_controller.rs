@@ -0,0 +1,163 @@
+use std::fs;
+use std::path::Path;
+
+use nika_core::controllers::import_controller::ImportController;
+use nika_core::errors::NikaError;
+use nika_core::errors::StudyListError;
+use nika_core::importer::context::extract_context_words;
+use nika_core::importer::matching::MatchStatus;
+use nika_core::importer::matching::Matcher;
+use nika_core::importer::matching::QueryResult;
+use nika_core::importer::matching::DEFAULT_CONFIDENCE;
+use nika_core::importer::query::Query;
+use nika_core::importer::resolution::Resolutions;
+use nika_core::models::jmdict::JMdict;
+use nika_core::repositories::config_repository::ConfigRepository;
+use nika_core::repositories::list_repository::ListRepository;
+use tempfile::tempdir;
+
+fn setup() -> (ImportController, ConfigRepository, ListRepository) {
+    setup_with("words.json")
+}
+
+fn setup_with(fixture: &str) -> (ImportController, ConfigRepository, ListRepository) {
+    let lists_path = tempdir().unwrap().into_path();
+    let config_filepath = tempdir().unwrap().into_path().join("config.toml");
+
+    let words_path = Path::new("tests").join("fixtures").join(fixture);
+    let data: JMdict = serde_json::from_str(&fs::read_to_string(words_path).unwrap()).unwrap();
+
+    let senses = extract_context_words(&data.words);
+    let matcher = Matcher::new(data.words, senses);
+
+    let controller = ImportController::new(
+        matcher,
+        ConfigRepository::new(config_filepath.clone()),
+        ListRepository::new(lists_path.clone()),
+    );
+
+    (
+        controller,
+        ConfigRepository::new(config_filepath),
+        ListRepository::new(lists_path),
+    )
+}
+
+fn query(kanji: Option<&str>, kana: Option<&str>) -> Query {
+    Query::new(kanji.map(String::from), kana.map(String::from), None)
+}
+
+#[test]
+fn test_find_matches() {
+    let (controller, _, _) = setup();
+
+    let queries = vec![
+        query(Some("食べる"), Some("たべる")),
+        query(Some("空"), None),
+        query(None, Some("として")),
+    ];
+
+    let result = controller.find_matches(&queries, DEFAULT_CONFIDENCE, || {});
+
+    assert_eq!(
+        result.results,
+        vec![
+            QueryResult {
+                index: 0,
+                query: queries[0].clone(),
+                status: MatchStatus::Match("1358280".into()),
+                confidence: 1.0,
+            },
+            QueryResult {
+                index: 1,
+                query: queries[1].clone(),
+                status: MatchStatus::Missing,
+                confidence: 0.0,
+            },
+            QueryResult {
+                index: 2,
+                query: queries[2].clone(),
+                status: MatchStatus::Match("1008590".into()),
+                confidence: 1.0,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_find_matches_keeps_order() {
+    let (controller, _, _) = setup();
+
+    let queries = ["日本", "自由", "複雑", "食べる", "日本"]
+        .iter()
+        .map(|kanji| query(Some(kanji), None))
+        .collect::<Vec<Query>>();
+
+    for _ in 0..10 {
+        let result = controller.find_matches(&queries, DEFAULT_CONFIDENCE, || {});
+
+        assert_eq!(
+            result.merge(&Resolutions::default()),
+            vec!["1582710", "1318720", "1501350", "1358280"]
+        );
+    }
+}
+
+#[test]
+fn test_find_matches_confidence() {
+    let (controller, _, _) = setup_with("matcher_words.json");
+
+    let queries = vec![
+        Query::new(None, Some("こし".into()), None),
+        Query::new(
+            None,
+            Some("こし".into()),
+            Some("paper for recycling".into()),
+        ),
+    ];
+
+    let result = controller.find_matches(&queries, DEFAULT_CONFIDENCE, || {});
+
+    assert_eq!(
+        result.results[0].status,
+        MatchStatus::Conflict(vec![
+            "1288840".into(),
+            "1269890".into(),
+            "1269900".into(),
+            "1287400".into(),
+        ])
+    );
+    assert!(result.results[0].confidence < DEFAULT_CONFIDENCE);
+    assert_eq!(
+        result.results[1].status,
+        MatchStatus::Match("1269890".into())
+    );
+
+    let result = controller.find_matches(&queries, 1.0, || {});
+
+    assert_eq!(result.conflicts().count(), 2);
+}
+
+#[test]
+fn test_create_list() {
+    let (controller, config_repository, list_repository) = setup();
+
+    controller
+        .create_list("list1", vec!["1358280".into()])
+        .unwrap();
+    controller.create_list("list2", vec![]).unwrap();
+
+    assert_eq!(
+        list_repository.get_list("list1").unwrap().items,
+        vec!["1358280"]
+    );
+    assert_eq!(
+        config_repository.get_current_list().unwrap(),
+        Some("list1".into())
+    );
+
+    assert!(matches!(
+        controller.create_list("list1", vec![]).unwrap_err(),
+        NikaError::List(StudyListError::ListAlreadyExists)
+    ));
+}
@@ -0,0 +1,73 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use nika_core::controllers::kanji_controller::KanjiController;
+use nika_core::errors::KanjiError;
+use nika_core::errors::NikaError;
+use nika_core::models::kanjidic::KanjiCode;
+use nika_core::models::kanjidic::Kanjidic;
+use nika_core::models::radicals::Kradfile;
+use nika_core::models::radicals::Radicals;
+use nika_core::models::radicals::Radkfile;
+use nika_core::repositories::dictionary_repository::DictionaryRepository;
+use serde::de::DeserializeOwned;
+
+fn fixture<T: DeserializeOwned>(name: &str) -> T {
+    let path = Path::new("tests").join("fixtures").join(name);
+
+    serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+}
+
+fn setup() -> KanjiController {
+    let kanjidic: Kanjidic = fixture("kanjidic.json");
+    let radicals = Radicals::new(
+        fixture::<Kradfile>("kradfile.json"),
+        fixture::<Radkfile>("radkfile.json"),
+    );
+
+    let dictionary_repository = DictionaryRepository::from(HashMap::new(), HashMap::new())
+        .with_kanjidic(kanjidic)
+        .with_radical_data(radicals);
+
+    KanjiController::new(dictionary_repository)
+}
+
+#[test]
+fn test_kanji_with_radicals() {
+    let controller = setup();
+
+    let kanji = controller.kanji_with_radicals(&["氵", "木"]).unwrap();
+
+    assert_eq!(kanji, vec!["沐", "淋"]);
+}
+
+#[test]
+fn test_unknown_radical() {
+    let controller = setup();
+
+    let result = controller.kanji_with_radicals(&["木", "図"]);
+
+    assert!(matches!(
+        result,
+        Err(NikaError::Kanji(KanjiError::UnknownRadical(radical))) if radical == "図"
+    ));
+}
+
+#[test]
+fn test_components() {
+    let controller = setup();
+
+    assert_eq!(controller.components("海"), vec!["ノ", "毋", "氵", "𠂉"]);
+    assert_eq!(controller.kanji("海").unwrap().misc.stroke_counts, vec![9]);
+}
+
+#[test]
+fn test_kanji_with_code() {
+    let controller = setup();
+
+    let kanji = controller.kanji_with_code(&KanjiCode::Reference("heisig".into(), "12".into()));
+
+    assert_eq!(kanji.len(), 1);
+    assert_eq!(kanji[0].literal, "日");
+}
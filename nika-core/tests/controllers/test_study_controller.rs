@@ -6,6 +6,10 @@ use nika_core::controllers::study_controller::StudyController;
 use nika_core::errors::NikaError;
 use nika_core::errors::StudyListError;
 use nika_core::models::jmdict::JMdict;
+use nika_core::models::kanjidic::Kanjidic;
+use nika_core::models::study_list::ItemKind;
+use nika_core::models::study_list::ItemStatus;
+use nika_core::models::study_list::StudyConfig;
 use nika_core::models::study_list::StudyList;
 use nika_core::repositories::config_repository::ConfigRepository;
 use nika_core::repositories::dictionary_repository::DictionaryRepository;
@@ -36,7 +40,11 @@ fn setup() -> StudyController {
 
     let list_repository = ListRepository::new(lists_path);
     let config_repository = ConfigRepository::new(config_filepath);
-    let dictionary_repository = DictionaryRepository::from(words, tags);
+    let kanjidic_path = Path::new("tests").join("fixtures").join("kanjidic.json");
+    let kanjidic: Kanjidic =
+        serde_json::from_str(&fs::read_to_string(kanjidic_path).unwrap()).unwrap();
+
+    let dictionary_repository = DictionaryRepository::from(words, tags).with_kanjidic(kanjidic);
 
     StudyController::new(dictionary_repository, config_repository, list_repository)
 }
@@ -206,3 +214,97 @@ fn test_list() {
     assert!(lists.contains(&study_list2));
     assert_eq!(lists.len(), 2);
 }
+
+#[test]
+fn test_mark() {
+    let controller = setup();
+
+    let study_list = StudyList::new("list", vec!["1358280".into(), "1582710".into()]);
+    controller.add(study_list).unwrap();
+
+    let id = controller
+        .mark("list", "食べる", Some(ItemStatus::Known))
+        .unwrap();
+    assert_eq!(id, "1358280");
+
+    controller
+        .mark("list", "1582710", Some(ItemStatus::Skipped))
+        .unwrap();
+
+    let study_list = controller.list("list").unwrap();
+    assert_eq!(
+        study_list.items_with_status(ItemStatus::Known),
+        vec!["1358280"]
+    );
+    assert_eq!(
+        study_list.items_with_status(ItemStatus::Skipped),
+        vec!["1582710"]
+    );
+
+    controller.mark("list", "食べる", None).unwrap();
+
+    let study_list = controller.list("list").unwrap();
+    assert!(study_list.items_with_status(ItemStatus::Known).is_empty());
+}
+
+#[test]
+fn test_mark_missing_item() {
+    let controller = setup();
+
+    let study_list = StudyList::new("list", vec!["1358280".into()]);
+    controller.add(study_list).unwrap();
+
+    let err = controller
+        .mark("list", "日本", Some(ItemStatus::Known))
+        .unwrap_err();
+    assert!(matches!(err, NikaError::List(StudyListError::ItemNotFound)));
+}
+
+#[test]
+fn test_study_kanji() {
+    let controller = setup();
+
+    let items = controller.grade_items(1);
+    assert_eq!(items, vec!["日", "本", "森", "林"]);
+
+    let study_list = StudyList::new("grade-1", items).with_kind(ItemKind::Kanji);
+    controller.add(study_list).unwrap();
+    controller
+        .update_config(
+            "grade-1",
+            StudyConfig {
+                current_index: 1,
+                items_per_day: 2,
+            },
+        )
+        .unwrap();
+
+    let literals = |daily| {
+        controller
+            .study_kanji("grade-1", daily)
+            .unwrap()
+            .iter()
+            .map(|kanji| kanji.literal.clone())
+            .collect::<Vec<String>>()
+    };
+
+    assert_eq!(literals(true), vec!["本", "森"]);
+    assert_eq!(literals(false).len(), 4);
+
+    let literal = controller
+        .mark("grade-1", "本", Some(ItemStatus::Known))
+        .unwrap();
+    assert_eq!(literal, "本");
+    assert_eq!(controller.list("grade-1").unwrap().kind, ItemKind::Kanji);
+}
+
+#[test]
+fn test_study_unknown_kanji() {
+    let controller = setup();
+
+    let study_list =
+        StudyList::new("kanji", vec!["日".into(), "鬱".into()]).with_kind(ItemKind::Kanji);
+    controller.add(study_list).unwrap();
+
+    assert!(controller.study_kanji("kanji", false).is_err());
+}
@@ -1,13 +1,25 @@
+use std::fs;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
 use anyhow::Error;
 use anyhow::Result;
 use clap::Parser;
 use clap::Subcommand;
 use nika::app::init_folders;
+use nika::commands::AnalyzeArgs;
+use nika::commands::ImportArgs;
+use nika::commands::KanjiArgs;
 use nika::commands::ProgressArgs;
 use nika::commands::RandomArgs;
 use nika::commands::SearchArgs;
+use nika::commands::SimilarArgs;
 use nika::commands::StudyArgs;
+use nika::config::CONFIG_PATH;
+use nika::config::WORDS_BIN_PATH;
 use nika::handlers::CommandHandler;
+use nika::messages::OUTDATED_DICTIONARY;
+use nika_core::repositories::config_repository::ConfigRepository;
 
 #[derive(Parser)]
 #[command(author, version, about, long_about = "<Long About>")]
@@ -26,6 +38,46 @@ enum Command {
     Progress(ProgressArgs),
     /// Show a random word or kanji
     Random(RandomArgs),
+    /// Import a word list as a study list
+    Import(ImportArgs),
+    /// Split a sentence into the words of the dictionary
+    Analyze(AnalyzeArgs),
+    /// Show the homophones, the words sharing a kanji and the synonyms of a
+    /// word
+    Similar(SimilarArgs),
+    /// Show a kanji with its components, or find the kanji by their radicals
+    Kanji(KanjiArgs),
+}
+
+impl Command {
+    /// Whether the command reads the data generated by `nika-updater`.
+    fn reads_dictionary(&self) -> bool {
+        !matches!(self, Command::Progress(_))
+    }
+}
+
+/// Warns when the dictionary is older than the update interval. The check is
+/// best-effort, it never stops the command.
+fn check_dictionary_age() {
+    let Ok(mut policy) = ConfigRepository::new(CONFIG_PATH.to_path_buf()).update_policy() else {
+        return;
+    };
+    let Ok(now) = SystemTime::now().duration_since(UNIX_EPOCH) else {
+        return;
+    };
+
+    // dictionaries generated before the last update was recorded
+    if policy.last_check.is_none() {
+        policy.last_check = fs::metadata(WORDS_BIN_PATH.as_path())
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+            .map(|modified| modified.as_secs());
+    }
+
+    if policy.is_stale(now.as_secs()) {
+        eprintln!("{}\n", OUTDATED_DICTIONARY);
+    }
 }
 
 fn run() -> Result<(), Error> {
@@ -33,11 +85,19 @@ fn run() -> Result<(), Error> {
 
     let cli = Cli::parse();
 
+    if cli.commands.reads_dictionary() {
+        check_dictionary_age();
+    }
+
     match &cli.commands {
         Command::Search(args) => args.handle(),
         Command::Study(args) => args.handle(),
         Command::Progress(args) => args.handle(),
         Command::Random(args) => args.handle(),
+        Command::Import(args) => args.handle(),
+        Command::Analyze(args) => args.handle(),
+        Command::Similar(args) => args.handle(),
+        Command::Kanji(args) => args.handle(),
     }
 }
 
@@ -1,19 +1,39 @@
 use std::collections::HashMap;
 use std::fs;
 use std::fs::File;
+use std::io::BufReader;
 use std::io::Read;
+use std::path::Path;
 use std::path::PathBuf;
-use std::process::Command;
-use std::process::Stdio;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
 
 use anyhow::anyhow;
 use anyhow::Context;
 use anyhow::Result;
+use clap::Args;
+use clap::Parser;
+use clap::Subcommand;
 use flate2::read::GzDecoder;
 use nika::config::*;
+use nika::schedule;
+use nika::schedule::Scheduler;
+use nika_core::importer::accent;
+use nika_core::importer::context::extract_context_words;
+use nika_core::importer::context::SenseMap;
+use nika_core::importer::frequency;
+use nika_core::importer::kanjivg;
+use nika_core::importer::matching::Matcher;
+use nika_core::importer::tatoeba;
 use nika_core::models::jmdict::JMdict;
+use nika_core::models::jmdict::Language3Letter;
+use nika_core::models::jmdict::Tag;
 use nika_core::models::jmdict::Word;
 use nika_core::models::kanjidic::Kanjidic;
+use nika_core::models::radicals::Kradfile;
+use nika_core::models::radicals::Radicals;
+use nika_core::models::radicals::Radkfile;
+use nika_core::repositories::config_repository::ConfigRepository;
 use rayon::prelude::IntoParallelIterator;
 use rayon::prelude::ParallelIterator;
 use reqwest::header::USER_AGENT;
@@ -30,6 +50,57 @@ use tracing_subscriber::prelude::__tracing_subscriber_SubscriberExt;
 use tracing_subscriber::util::SubscriberInitExt;
 use tracing_subscriber::Layer;
 use tracing_subscriber::Registry;
+use zip::ZipArchive;
+
+#[derive(Parser)]
+#[command(author, version, about = "Update the Nika dictionary")]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<UpdaterCommand>,
+    /// Frequency list (surface, reading and rank separated by tabs) used to
+    /// rank the words, kept for the next updates
+    #[arg(long = "frequency")]
+    frequency: Option<PathBuf>,
+    /// Pitch accent dictionary (word, reading and accent patterns separated by
+    /// tabs, e.g. the Kanjium `accents.txt`) of the readings, kept for the
+    /// next updates
+    #[arg(long = "accents", value_name = "FILE")]
+    accents: Option<PathBuf>,
+    /// Directory of a Tatoeba export (`sentences.csv` and `jpn_indices.csv`)
+    /// with the example sentences of the words
+    #[arg(long = "tatoeba", value_name = "DIR")]
+    tatoeba: Option<PathBuf>,
+    /// KanjiVG archive with the stroke order of the kanji: the XML file with
+    /// all the kanji (may be gzipped), or the zip archive or a directory of
+    /// SVG files
+    #[arg(long = "kanjivg", value_name = "PATH")]
+    kanjivg: Option<PathBuf>,
+}
+
+#[derive(Subcommand)]
+enum UpdaterCommand {
+    /// Manage the automatic updates
+    Schedule(ScheduleArgs),
+}
+
+#[derive(Args)]
+struct ScheduleArgs {
+    #[command(subcommand)]
+    action: ScheduleAction,
+    /// Service used to run the updater
+    #[arg(short = 's', long = "scheduler", value_enum, default_value = "cron")]
+    scheduler: Scheduler,
+}
+
+#[derive(Subcommand)]
+enum ScheduleAction {
+    /// Run the updater at the configured interval
+    Install,
+    /// Stop running the updater automatically
+    Remove,
+    /// Show the scheduled update and the dictionary age
+    Status,
+}
 
 #[derive(Serialize, Deserialize, Debug)]
 struct Release {
@@ -44,7 +115,27 @@ struct Asset {
     browser_download_url: String,
 }
 
-fn find_release_url() -> Result<(String, String)> {
+/// Download URLs of the dictionaries of the latest release.
+struct ReleaseUrls {
+    jmdict: String,
+    kanjidic: String,
+    /// KRADFILE and RADKFILE, with the components of the kanji.
+    radicals: Option<(String, String)>,
+}
+
+/// Finds the `.tgz` asset named `<prefix>-<version>...`, skipping the variants
+/// such as `<prefix>-common-<version>`.
+fn find_asset<'a>(assets: &[&'a Asset], prefix: &str) -> Option<&'a Asset> {
+    assets.iter().copied().find(|asset| {
+        asset
+            .name
+            .strip_prefix(prefix)
+            .and_then(|rest| rest.strip_prefix('-'))
+            .is_some_and(|rest| rest.starts_with(|c: char| c.is_ascii_digit()))
+    })
+}
+
+fn find_release_url(languages: &[Language3Letter]) -> Result<ReleaseUrls> {
     let client = reqwest::blocking::Client::new();
     let response = client
         .get(DICTIONARY_RELEASE_URL)
@@ -62,20 +153,38 @@ fn find_release_url() -> Result<(String, String)> {
         .filter(|asset| asset.name.ends_with(".tgz"))
         .collect::<Vec<&Asset>>();
 
-    let jmdict_asset = tgz_assets
-        .iter()
-        .find(|asset| asset.name.contains("jmdict-eng") && !asset.name.contains("common"));
-
-    let kanjidic_asset = tgz_assets
-        .iter()
-        .find(|asset| asset.name.contains("kanjidic2-en"));
+    // a single language may have its own build, otherwise all the languages
+    // are downloaded and filtered when generating the binary
+    let jmdict_asset = match languages {
+        [language] => find_asset(&tgz_assets, &format!("jmdict-{}", language)),
+        _ => None,
+    }
+    .or_else(|| find_asset(&tgz_assets, "jmdict-all"));
+
+    let kanjidic_asset = if languages.iter().all(|lang| lang == "eng") {
+        find_asset(&tgz_assets, "kanjidic2-en")
+    } else {
+        find_asset(&tgz_assets, "kanjidic2-all")
+    };
+
+    let radicals = match (
+        find_asset(&tgz_assets, "kradfile"),
+        find_asset(&tgz_assets, "radkfile"),
+    ) {
+        (Some(kradfile), Some(radkfile)) if kradfile.size > 0 && radkfile.size > 0 => Some((
+            kradfile.browser_download_url.clone(),
+            radkfile.browser_download_url.clone(),
+        )),
+        _ => None,
+    };
 
     if let (Some(jmdict), Some(kanjidic)) = (jmdict_asset, kanjidic_asset) {
         if jmdict.size > 0 && kanjidic.size > 0 {
-            return Ok((
-                jmdict.browser_download_url.clone(),
-                kanjidic.browser_download_url.clone(),
-            ));
+            return Ok(ReleaseUrls {
+                jmdict: jmdict.browser_download_url.clone(),
+                kanjidic: kanjidic.browser_download_url.clone(),
+                radicals,
+            });
         }
         return Err(anyhow!("Empty resources"));
     }
@@ -132,80 +241,227 @@ fn generate_bincode_kanjidic(data: &Kanjidic) -> Result<()> {
     Ok(())
 }
 
-fn generate_bincode_jmdict(data: &JMdict) -> Result<()> {
-    let words: HashMap<String, Word> = data
+fn generate_bincode_radicals(kradfile: Kradfile, radkfile: Radkfile) -> Result<()> {
+    let file = File::create(RADICALS_BIN_PATH.as_path())?;
+    let mut writer = std::io::BufWriter::new(file);
+    bincode::serialize_into(&mut writer, &Radicals::new(kradfile, radkfile))
+        .with_context(|| "Failed to serialize radicals")?;
+
+    Ok(())
+}
+
+fn read_accents() -> Result<accent::AccentMap> {
+    let content = fs::read_to_string(ACCENTS_LIST_PATH.as_path())
+        .with_context(|| "Failed to read the accent dictionary")?;
+    let entries = accent::parse_tsv(&content)?;
+
+    Ok(accent::accent_map(&entries))
+}
+
+/// Words with the glosses of the configured languages only, and the pitch
+/// accents of their readings. The context words, the frequencies and the
+/// examples are generated from these words, so that their senses line up with
+/// the ones of the dictionary.
+fn filter_words(
+    data: &JMdict,
+    languages: &[Language3Letter],
+    accents: Option<&accent::AccentMap>,
+) -> Vec<Word> {
+    let mut words: Vec<Word> = data
         .words
         .clone()
         .into_par_iter()
-        .map(|word| (word.id.clone(), word))
+        .map(|mut word| {
+            word.retain_languages(languages);
+            word
+        })
         .collect();
 
+    if let Some(accents) = accents {
+        let count = accent::attach_accents(&mut words, accents);
+        debug!("Attached pitch accents to {} readings", count);
+    }
+
+    words
+}
+
+fn generate_bincode_jmdict(words: &[Word], tags: &HashMap<Tag, String>) -> Result<()> {
+    let words: HashMap<&String, &Word> = words.iter().map(|word| (&word.id, word)).collect();
+
     let file = File::create(WORDS_BIN_PATH.as_path())?;
     let mut writer = std::io::BufWriter::new(file);
     bincode::serialize_into(&mut writer, &words).with_context(|| "Failed to serialize words")?;
 
     let file = File::create(TAGS_BIN_PATH.as_path())?;
     let mut writer = std::io::BufWriter::new(file);
-    bincode::serialize_into(&mut writer, &data.tags).with_context(|| "Failed to serialize tags")?;
+    bincode::serialize_into(&mut writer, tags).with_context(|| "Failed to serialize tags")?;
+
+    Ok(())
+}
+
+fn generate_context_words(words: &[Word]) -> Result<SenseMap> {
+    let senses = extract_context_words(words);
+
+    let senses_json =
+        serde_json::to_string(&senses).with_context(|| "Failed to serialize words senses")?;
+    fs::write(SENSES_PATH.as_path(), senses_json).with_context(|| "Failed to save words senses")?;
+
+    Ok(senses)
+}
+
+fn generate_bincode_frequencies(words: Vec<Word>, senses: SenseMap) -> Result<()> {
+    let content = fs::read_to_string(FREQUENCY_LIST_PATH.as_path())
+        .with_context(|| "Failed to read the frequency list")?;
+    let entries = frequency::parse_tsv(&content)?;
+
+    let matcher = Matcher::new(words, senses);
+    let frequencies = frequency::rank_words(&entries, &matcher);
+
+    debug!(
+        "Ranked {} words of {} entries",
+        frequencies.len(),
+        entries.len()
+    );
+
+    let file = File::create(FREQUENCY_BIN_PATH.as_path())?;
+    let mut writer = std::io::BufWriter::new(file);
+    bincode::serialize_into(&mut writer, &frequencies)
+        .with_context(|| "Failed to serialize frequencies")?;
 
     Ok(())
 }
 
-fn extract_context_words(words: &JMdict) -> Result<()> {
-    let tmp_senses_path = app_cache_dir().join("senses.json");
-    let out_senses_path = app_data_dir().join("senses.json");
+fn generate_bincode_examples(words: &[Word], tatoeba_dir: &Path) -> Result<()> {
+    let indices = fs::read_to_string(tatoeba_dir.join("jpn_indices.csv"))
+        .with_context(|| "Failed to read the Tatoeba indices")?;
+    let entries = tatoeba::parse_indices(&indices)?;
+
+    let file = File::open(tatoeba_dir.join("sentences.csv"))
+        .with_context(|| "Failed to open the Tatoeba sentences")?;
+    let sentences =
+        tatoeba::read_sentences(BufReader::new(file), &tatoeba::sentence_ids(&entries))?;
+
+    let examples = tatoeba::link_examples(&entries, &sentences, words);
 
-    if !out_senses_path.exists() {
-        let mut words_senses = HashMap::new();
+    debug!(
+        "Linked examples to {} words from {} sentences",
+        examples.len(),
+        entries.len()
+    );
 
-        for word in &words.words {
-            let mut senses = vec![];
+    let file = File::create(EXAMPLES_BIN_PATH.as_path())?;
+    let mut writer = std::io::BufWriter::new(file);
+    bincode::serialize_into(&mut writer, &examples)
+        .with_context(|| "Failed to serialize examples")?;
+
+    Ok(())
+}
+
+/// Content of the KanjiVG files of the archive: the XML file, decompressed
+/// when gzipped, or the SVG files of the directory or of the zip archive.
+fn read_kanjivg(path: &Path) -> Result<Vec<String>> {
+    if path.is_dir() {
+        let mut contents = Vec::new();
 
-            for sense in &word.sense {
-                let glosses = sense
-                    .gloss
-                    .iter()
-                    .map(|gloss| gloss.text.clone())
-                    .collect::<Vec<String>>();
+        for entry in fs::read_dir(path)? {
+            let entry_path = entry?.path();
 
-                senses.push(glosses);
+            if entry_path.extension().is_some_and(|ext| ext == "svg") {
+                contents.push(fs::read_to_string(entry_path)?);
             }
+        }
 
-            words_senses.insert(&word.id, senses);
+        return Ok(contents);
+    }
+
+    if path.extension().is_some_and(|ext| ext == "zip") {
+        let file = File::open(path).with_context(|| "Failed to open the KanjiVG archive")?;
+        let mut archive = ZipArchive::new(file)?;
+        let mut contents = Vec::new();
+
+        for i in 0..archive.len() {
+            let mut entry = archive.by_index(i)?;
+
+            if entry.name().ends_with(".svg") {
+                let mut content = String::new();
+                entry.read_to_string(&mut content)?;
+                contents.push(content);
+            }
         }
 
-        let words_senses_json = serde_json::to_string(&words_senses)
-            .with_context(|| "Failed to serialize words senses")?;
-        fs::write(&tmp_senses_path, words_senses_json)
-            .with_context(|| "Failed to save temporary words senses")?;
-
-        Command::new("extract_words")
-            .arg("-i")
-            .arg(tmp_senses_path)
-            .arg("-o")
-            .arg(out_senses_path)
-            .stdout(Stdio::piped())
-            .spawn()
-            .expect("Failed to execute process")
-            .wait()
-            .with_context(|| "Failed to extract context words")?;
+        return Ok(contents);
+    }
+
+    let bytes = fs::read(path).with_context(|| "Failed to read the KanjiVG archive")?;
+
+    let content = if path.extension().is_some_and(|ext| ext == "gz") {
+        let mut content = String::new();
+        GzDecoder::new(&bytes[..]).read_to_string(&mut content)?;
+        content
+    } else {
+        String::from_utf8(bytes)?
+    };
+
+    Ok(vec![content])
+}
+
+fn generate_bincode_strokes(kanjivg_path: &Path) -> Result<()> {
+    let mut strokes = kanjivg::StrokeMap::new();
+
+    for content in read_kanjivg(kanjivg_path)? {
+        strokes.extend(kanjivg::parse_strokes(&content));
     }
 
+    debug!("Read the strokes of {} kanji", strokes.len());
+
+    let file = File::create(STROKES_BIN_PATH.as_path())?;
+    let mut writer = std::io::BufWriter::new(file);
+    bincode::serialize_into(&mut writer, &strokes)
+        .with_context(|| "Failed to serialize strokes")?;
+
     Ok(())
 }
 
-fn run() -> Result<()> {
+fn run(
+    frequency_list: Option<&PathBuf>,
+    accents_list: Option<&PathBuf>,
+    tatoeba_dir: Option<&PathBuf>,
+    kanjivg_path: Option<&PathBuf>,
+) -> Result<()> {
+    let languages = ConfigRepository::new(CONFIG_PATH.to_path_buf()).languages()?;
+
+    if let Some(path) = frequency_list {
+        fs::copy(path, FREQUENCY_LIST_PATH.as_path())
+            .with_context(|| format!("Failed to copy the frequency list {:?}", path))?;
+    }
+
+    if let Some(path) = accents_list {
+        fs::copy(path, ACCENTS_LIST_PATH.as_path())
+            .with_context(|| format!("Failed to copy the accent dictionary {:?}", path))?;
+    }
+
     info!("[1/5] Finding the latest release...");
-    let (jmdict_url, kanjidic_url) = find_release_url()?;
+    let urls = find_release_url(&languages)?;
 
     let dest_dir = app_cache_dir().join("data");
     fs::create_dir_all(&dest_dir).expect("Failed to create data directory");
 
     info!("[2/5] Downloading JMDict data...");
-    let jmdict_path = download_and_extract_tgz(&jmdict_url, &dest_dir)?;
+    let jmdict_path = download_and_extract_tgz(&urls.jmdict, &dest_dir)?;
 
     info!("[2/5] Downloading Kanjidic2 data...");
-    let kanjidic_path = download_and_extract_tgz(&kanjidic_url, &dest_dir)?;
+    let kanjidic_path = download_and_extract_tgz(&urls.kanjidic, &dest_dir)?;
+
+    let radicals_paths = match &urls.radicals {
+        Some((kradfile_url, radkfile_url)) => {
+            info!("[2/5] Downloading KRADFILE and RADKFILE data...");
+            Some((
+                download_and_extract_tgz(kradfile_url, &dest_dir)?,
+                download_and_extract_tgz(radkfile_url, &dest_dir)?,
+            ))
+        }
+        None => None,
+    };
 
     info!("[3/5] Parsing JMDict data...");
     let jmdict_data = parse_json::<JMdict>(&jmdict_path)?;
@@ -213,21 +469,105 @@ fn run() -> Result<()> {
     info!("[3/5] Parsing Kanjidic2 data...");
     let kanjidic_data = parse_json::<Kanjidic>(&kanjidic_path)?;
 
+    let accents = if ACCENTS_LIST_PATH.exists() {
+        info!("[3/5] Parsing the accent dictionary...");
+        Some(read_accents()?)
+    } else {
+        None
+    };
+
     info!("[4/5] Generating JMDict binary...");
-    generate_bincode_jmdict(&jmdict_data)?;
+    let words = filter_words(&jmdict_data, &languages, accents.as_ref());
+    generate_bincode_jmdict(&words, &jmdict_data.tags)?;
 
     info!("[4/5] Generating Kanjidic2 binary...");
     generate_bincode_kanjidic(&kanjidic_data)?;
 
+    if let Some((kradfile_path, radkfile_path)) = &radicals_paths {
+        info!("[4/5] Generating radicals binary...");
+        generate_bincode_radicals(parse_json(kradfile_path)?, parse_json(radkfile_path)?)?;
+    }
+
     info!("[5/5] Extracting context words...");
-    extract_context_words(&jmdict_data)?;
+    let senses = generate_context_words(&words)?;
+
+    if let Some(dir) = tatoeba_dir {
+        info!("[5/5] Linking the Tatoeba examples...");
+        generate_bincode_examples(&words, dir)?;
+    }
+
+    if FREQUENCY_LIST_PATH.exists() {
+        info!("[5/5] Ranking words by frequency...");
+        generate_bincode_frequencies(words, senses)?;
+    }
+
+    if let Some(path) = kanjivg_path {
+        info!("[5/5] Reading the KanjiVG strokes...");
+        generate_bincode_strokes(path)?;
+    }
+
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    ConfigRepository::new(CONFIG_PATH.to_path_buf()).set_last_check(now)?;
 
     info!("Update completed successfully.");
 
     Ok(())
 }
 
+fn run_schedule(args: &ScheduleArgs) -> Result<()> {
+    let config_repository = ConfigRepository::new(CONFIG_PATH.to_path_buf());
+    let policy = config_repository.update_policy()?;
+
+    match args.action {
+        ScheduleAction::Install => {
+            let updater = std::env::current_exe()?;
+            schedule::install(args.scheduler, &updater, policy.interval_days)?;
+            println!(
+                "Update scheduled every {} days",
+                schedule::effective_interval(args.scheduler, policy.interval_days)
+            );
+        }
+        ScheduleAction::Remove => {
+            schedule::remove(args.scheduler)?;
+            println!("Scheduled update removed");
+        }
+        ScheduleAction::Status => {
+            let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+
+            match schedule::status(args.scheduler)? {
+                Some(entry) => println!("Scheduled: {}", entry),
+                None => println!("Scheduled: no"),
+            }
+
+            match policy.last_check {
+                Some(last_check) => {
+                    println!(
+                        "Last update: {} days ago",
+                        now.saturating_sub(last_check) / (24 * 60 * 60)
+                    );
+                }
+                None => println!("Last update: never"),
+            }
+
+            println!("Outdated: {}", policy.is_stale(now));
+        }
+    }
+
+    Ok(())
+}
+
 fn main() {
+    let cli = Cli::parse();
+
+    if let Some(UpdaterCommand::Schedule(args)) = &cli.command {
+        if let Err(error) = run_schedule(args) {
+            eprintln!("Error: {}", error);
+            std::process::exit(1);
+        }
+
+        std::process::exit(0);
+    }
+
     let cache_dir = app_cache_dir();
     let file_appender = tracing_appender::rolling::daily(&cache_dir, "update.log");
 
@@ -250,7 +590,12 @@ fn main() {
         )
         .init();
 
-    if let Err(error) = run() {
+    if let Err(error) = run(
+        cli.frequency.as_ref(),
+        cli.accents.as_ref(),
+        cli.tatoeba.as_ref(),
+        cli.kanjivg.as_ref(),
+    ) {
         info!(
             "Update failed. Please check the log file for more details at {:?}",
             cache_dir
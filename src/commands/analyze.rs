@@ -0,0 +1,134 @@
+use std::fs;
+use std::io;
+use std::io::Read;
+use std::path::PathBuf;
+
+use anyhow::Error;
+use anyhow::Result;
+use clap::Args;
+use nika_core::analysis::tokenizer::Token;
+use nika_core::controllers::analysis_controller::AnalysisController;
+use nika_core::models::jmdict::Language3Letter;
+use nika_core::models::jmdict::Word;
+use nika_core::repositories::config_repository::ConfigRepository;
+use nika_core::repositories::dictionary_repository::DictionaryRepository;
+use nika_core::repositories::list_repository::ListRepository;
+
+use crate::config::app_data_dir;
+use crate::config::CONFIG_PATH;
+use crate::config::TAGS_BIN_PATH;
+use crate::config::WORDS_BIN_PATH;
+use crate::handlers::CommandHandler;
+use crate::utils::display::reading;
+
+#[derive(Args)]
+pub struct AnalyzeArgs {
+    /// The sentence to split into words, read from the standard input when
+    /// missing
+    #[arg(conflicts_with = "unknown")]
+    sentence: Option<String>,
+
+    /// List the words of the text file not marked as known in the study
+    /// lists, from the most frequent one
+    #[arg(short = 'u', long = "unknown", value_name = "FILE")]
+    unknown: Option<PathBuf>,
+
+    /// Create a study list with the unknown words
+    #[arg(long = "create", value_name = "NAME", requires = "unknown")]
+    create: Option<String>,
+}
+
+impl CommandHandler for AnalyzeArgs {
+    fn handle(&self) -> Result<(), Error> {
+        let dictionary_repository =
+            DictionaryRepository::new(&WORDS_BIN_PATH.as_path(), &TAGS_BIN_PATH.as_path())?;
+        let list_repository = ListRepository::new(app_data_dir().join("lists"));
+        let config_repository = ConfigRepository::new(CONFIG_PATH.to_path_buf());
+        let languages = config_repository.languages()?;
+        let romaji = config_repository.display_settings()?.romaji;
+        let controller =
+            AnalysisController::new(dictionary_repository, config_repository, list_repository);
+
+        if let Some(path) = &self.unknown {
+            let text = fs::read_to_string(path)?;
+            let unknown = controller.unknown_words(&text)?;
+
+            println!("{} unknown words\n", unknown.len());
+
+            for (word, count) in &unknown {
+                println!("{:>5}  {}", count, word_entry(word, &languages, romaji));
+            }
+
+            if let Some(name) = &self.create {
+                let items = unknown.iter().map(|(word, _)| word.id.clone()).collect();
+                controller.create_list(name, items)?;
+
+                println!("\nStudy list '{}' created", name);
+            }
+
+            return Ok(());
+        }
+
+        let text = match &self.sentence {
+            Some(sentence) => sentence.clone(),
+            None => {
+                let mut text = String::new();
+                io::stdin().read_to_string(&mut text)?;
+                text
+            }
+        };
+
+        let tokenizer = controller.tokenizer();
+
+        for line in text.lines().filter(|line| !line.trim().is_empty()) {
+            println!("{}\n", line.trim());
+
+            for token in tokenizer.tokenize(line) {
+                println!("   {}", token_entry(&token, &languages, romaji));
+            }
+
+            println!();
+        }
+
+        Ok(())
+    }
+}
+
+fn first_gloss<'a>(word: &'a Word, languages: &[Language3Letter]) -> &'a str {
+    word.sense
+        .iter()
+        .flat_map(|sense| sense.glosses(languages))
+        .map(|gloss| gloss.text.as_str())
+        .next()
+        .unwrap_or("")
+}
+
+fn word_entry(word: &Word, languages: &[Language3Letter], romaji: bool) -> String {
+    let form = match (word.kanji.first(), word.kana.first()) {
+        (Some(kanji), Some(kana)) => format!("{} 「{}」", kanji.text, reading(&kana.text, romaji)),
+        (None, Some(kana)) => reading(&kana.text, romaji),
+        _ => String::new(),
+    };
+
+    format!("{:<24}\t{}", form, first_gloss(word, languages))
+}
+
+fn token_entry(token: &Token, languages: &[Language3Letter], romaji: bool) -> String {
+    let Some(word) = token.word else {
+        return token.surface.clone();
+    };
+
+    let form = match token.reading() {
+        Some(kana) if kana != token.dictionary_form => {
+            format!("{} 「{}」", token.dictionary_form, reading(kana, romaji))
+        }
+        _ => reading(&token.dictionary_form, romaji),
+    };
+
+    format!(
+        "{:<12}\t{:<24}\t{}",
+        token.surface,
+        form,
+        first_gloss(word, languages)
+    )
+}
@@ -0,0 +1,314 @@
+use std::fs;
+use std::io;
+use std::io::Write;
+use std::path::PathBuf;
+
+use anyhow::anyhow;
+use anyhow::Error;
+use anyhow::Result;
+use clap::Args;
+use clap::ValueEnum;
+use indicatif::ProgressBar;
+use nika_core::controllers::import_controller::ImportController;
+use nika_core::importer::context::SenseMap;
+use nika_core::importer::matching::Candidate;
+use nika_core::importer::matching::MatchStatus;
+use nika_core::importer::matching::Matcher;
+use nika_core::importer::matching::QueryResult;
+use nika_core::importer::matching::DEFAULT_CONFIDENCE;
+use nika_core::importer::query;
+use nika_core::importer::query::Query;
+use nika_core::importer::resolution::Resolutions;
+use nika_core::models::jmdict::Language3Letter;
+use nika_core::repositories::config_repository::ConfigRepository;
+use nika_core::repositories::dictionary_repository::DictionaryRepository;
+use nika_core::repositories::list_repository::ListRepository;
+use serde_derive::Serialize;
+
+use crate::config::app_data_dir;
+use crate::config::CONFIG_PATH;
+use crate::config::SENSES_PATH;
+use crate::config::TAGS_BIN_PATH;
+use crate::config::WORDS_BIN_PATH;
+use crate::handlers::CommandHandler;
+use crate::messages::MISSING_SENSES;
+use crate::messages::OUTDATED_SENSES;
+
+#[derive(Clone, ValueEnum)]
+pub enum ImportFormat {
+    Json,
+    Csv,
+}
+
+#[derive(Args)]
+pub struct ImportArgs {
+    /// File with the words to import (kanji, kana and meaning)
+    file: PathBuf,
+    /// Name of the study list (defaults to the file name)
+    #[arg(short = 'n', long = "name")]
+    name: Option<String>,
+    /// Format of the file (defaults to the file extension)
+    #[arg(short = 'f', long = "format", value_enum)]
+    format: Option<ImportFormat>,
+    /// Write the report of the missing and conflicting words to a file
+    /// instead of the standard output
+    #[arg(short = 'r', long = "report")]
+    report: Option<PathBuf>,
+    /// Choose the words of the ambiguous entries
+    #[arg(short = 'i', long = "interactive")]
+    interactive: bool,
+    /// Minimum confidence to accept the most likely word of an ambiguous
+    /// entry, from 0 to 1 (values above 1 accept only the unambiguous ones)
+    #[arg(short = 'c', long = "confidence", default_value_t = DEFAULT_CONFIDENCE)]
+    confidence: f64,
+    /// File with the choices for the ambiguous entries (defaults to
+    /// `<FILE>.resolutions.json`)
+    #[arg(long = "resolutions")]
+    resolutions: Option<PathBuf>,
+}
+
+enum Choice {
+    Words(Vec<usize>),
+    Skip,
+    Quit,
+}
+
+#[derive(Serialize)]
+struct ImportReport<'a> {
+    missing: Vec<&'a QueryResult>,
+    conflicts: Vec<&'a QueryResult>,
+}
+
+impl ImportArgs {
+    fn format(&self) -> Result<ImportFormat, Error> {
+        if let Some(format) = &self.format {
+            return Ok(format.clone());
+        }
+
+        match self.file.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => Ok(ImportFormat::Json),
+            Some("csv") => Ok(ImportFormat::Csv),
+            _ => Err(anyhow!("Unknown file format, use --format to specify it")),
+        }
+    }
+
+    fn resolutions_path(&self) -> PathBuf {
+        self.resolutions.clone().unwrap_or_else(|| {
+            let mut filename = self.file.clone().into_os_string();
+            filename.push(".resolutions.json");
+            PathBuf::from(filename)
+        })
+    }
+
+    fn list_name(&self) -> Result<String, Error> {
+        match &self.name {
+            Some(name) => Ok(name.clone()),
+            None => self
+                .file
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .ok_or_else(|| anyhow!("Could not determine the list name, use --name")),
+        }
+    }
+}
+
+impl CommandHandler for ImportArgs {
+    fn handle(&self) -> Result<(), Error> {
+        let name = self.list_name()?;
+
+        let content = fs::read_to_string(&self.file)?;
+        let queries = match self.format()? {
+            ImportFormat::Json => query::parse_json(&content)?,
+            ImportFormat::Csv => query::parse_csv(&content)?,
+        };
+
+        if !SENSES_PATH.exists() {
+            return Err(anyhow!(MISSING_SENSES));
+        }
+
+        let senses = serde_json::from_str::<SenseMap>(&fs::read_to_string(SENSES_PATH.as_path())?)
+            .map_err(|_| anyhow!(OUTDATED_SENSES))?;
+        let words = DictionaryRepository::new(&WORDS_BIN_PATH.as_path(), &TAGS_BIN_PATH.as_path())?
+            .into_words();
+
+        let config_repository = ConfigRepository::new(CONFIG_PATH.to_path_buf());
+        let languages = config_repository.languages()?;
+
+        let controller = ImportController::new(
+            Matcher::new(words, senses),
+            config_repository,
+            ListRepository::new(app_data_dir().join("lists")),
+        );
+
+        let pb = ProgressBar::new(queries.len() as u64);
+        let result = controller.find_matches(&queries, self.confidence, || pb.inc(1));
+        pb.finish_and_clear();
+
+        eprintln!(
+            "Matches: {}, Conflicts: {}, Missing: {}",
+            result.matches().count(),
+            result.conflicts().count(),
+            result.missing().count()
+        );
+
+        let resolutions_path = self.resolutions_path();
+        let mut resolutions = Resolutions::load(&resolutions_path)?;
+
+        if self.interactive {
+            let unresolved = result
+                .conflicts()
+                .filter(|c| resolutions.get(&c.query).is_none())
+                .collect::<Vec<&QueryResult>>();
+
+            for (i, conflict) in unresolved.iter().enumerate() {
+                let MatchStatus::Conflict(ids) = &conflict.status else {
+                    continue;
+                };
+
+                let candidates = controller.candidates(&conflict.query, ids);
+
+                eprintln!(
+                    "[{}/{}] {}",
+                    i + 1,
+                    unresolved.len(),
+                    format_query(&conflict.query)
+                );
+
+                match ask_choice(&candidates, &languages)? {
+                    Choice::Words(choices) => {
+                        let ids = choices
+                            .iter()
+                            .map(|&choice| candidates[choice].word.id.clone())
+                            .collect();
+
+                        resolutions.set(&conflict.query, ids);
+                        Resolutions::save(&resolutions_path, &resolutions)?;
+                    }
+                    Choice::Skip => continue,
+                    Choice::Quit => break,
+                }
+            }
+        }
+
+        let report = serde_json::to_string_pretty(&ImportReport {
+            missing: result.missing().collect(),
+            conflicts: result
+                .conflicts()
+                .filter(|c| resolutions.get(&c.query).is_none())
+                .collect(),
+        })?;
+
+        match &self.report {
+            Some(path) => fs::write(path, report)?,
+            None => println!("{}", report),
+        }
+
+        // after the report, which is kept when the list can't be created
+        controller.create_list(&name, result.merge(&resolutions))?;
+        eprintln!("List '{}' created", name);
+
+        Ok(())
+    }
+}
+
+fn format_query(query: &Query) -> String {
+    let text = [&query.kanji, &query.kana]
+        .iter()
+        .filter_map(|text| text.as_deref())
+        .collect::<Vec<&str>>()
+        .join(" ");
+
+    match &query.meaning {
+        Some(meaning) => format!("{} ({})", text, meaning),
+        None => text,
+    }
+}
+
+fn format_candidate(candidate: &Candidate, languages: &[Language3Letter]) -> String {
+    let word = candidate.word;
+    let kanji = word.kanji.first().map(|k| format!("{} ", k.text));
+    let readings = word
+        .kana
+        .iter()
+        .map(|k| k.text.as_str())
+        .collect::<Vec<&str>>()
+        .join("、");
+    let glosses = word
+        .sense
+        .iter()
+        .map(|s| {
+            s.glosses(languages)
+                .iter()
+                .take(3)
+                .map(|g| g.text.as_str())
+                .collect::<Vec<&str>>()
+                .join(", ")
+        })
+        .find(|glosses| !glosses.is_empty())
+        .unwrap_or_default();
+    format!(
+        "{}【{}】 {} ({}confidence {:.0}%)",
+        kanji.unwrap_or_default(),
+        readings,
+        glosses,
+        if word.is_common() { "common, " } else { "" },
+        candidate.confidence * 100.0
+    )
+}
+
+fn ask_choice(candidates: &[Candidate], languages: &[Language3Letter]) -> Result<Choice, Error> {
+    for (i, candidate) in candidates.iter().enumerate() {
+        eprintln!("  {}. {}", i + 1, format_candidate(candidate, languages));
+    }
+
+    loop {
+        eprint!("Select the words (e.g. 1 or 1,3), none with enter, [s]kip or [q]uit: ");
+        io::stderr().flush()?;
+
+        let mut input = String::new();
+
+        if io::stdin().read_line(&mut input)? == 0 {
+            return Ok(Choice::Quit);
+        }
+
+        match parse_choice(&input, candidates.len()) {
+            Some(choice) => {
+                eprintln!();
+                return Ok(choice);
+            }
+            None => eprintln!("Invalid selection"),
+        }
+    }
+}
+
+fn parse_choice(input: &str, count: usize) -> Option<Choice> {
+    match input.trim() {
+        "s" => Some(Choice::Skip),
+        "q" => Some(Choice::Quit),
+        input => input
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|n| !n.is_empty())
+            .map(|n| match n.parse::<usize>() {
+                Ok(n) if (1..=count).contains(&n) => Some(n - 1),
+                _ => None,
+            })
+            .collect::<Option<Vec<usize>>>()
+            .map(Choice::Words),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_choice() {
+        assert!(matches!(parse_choice("1, 3\n", 3), Some(Choice::Words(w)) if w == vec![0, 2]));
+        assert!(matches!(parse_choice("\n", 3), Some(Choice::Words(w)) if w.is_empty()));
+        assert!(matches!(parse_choice("s\n", 3), Some(Choice::Skip)));
+        assert!(matches!(parse_choice("q", 3), Some(Choice::Quit)));
+        assert!(parse_choice("4", 3).is_none());
+        assert!(parse_choice("0", 3).is_none());
+        assert!(parse_choice("x", 3).is_none());
+    }
+}
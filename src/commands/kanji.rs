@@ -0,0 +1,293 @@
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::anyhow;
+use anyhow::Error;
+use anyhow::Result;
+use clap::ArgGroup;
+use clap::Args;
+use nika_core::controllers::kanji_controller::KanjiController;
+use nika_core::importer::kanjivg::KANJIVG_SIZE;
+use nika_core::models::kanjidic::Kanji;
+use nika_core::models::kanjidic::KanjiCode;
+use nika_core::repositories::config_repository::ConfigRepository;
+use nika_core::repositories::dictionary_repository::DictionaryRepository;
+use nika_core::repositories::dictionary_repository::TagMap;
+use nika_core::repositories::dictionary_repository::WordMap;
+use nika_core::utils::strokes::flatten_path;
+use nika_core::utils::strokes::stroke_order_svg;
+use nika_core::utils::strokes::BrailleCanvas;
+
+use crate::config::CONFIG_PATH;
+use crate::config::KANJI_BIN_PATH;
+use crate::config::RADICALS_BIN_PATH;
+use crate::config::STROKES_BIN_PATH;
+use crate::handlers::CommandHandler;
+use crate::messages::MISSING_STROKES;
+use crate::utils::display::kanji_entry;
+
+/// Stroke order frames shown side by side.
+const FRAMES_PER_ROW: usize = 5;
+/// Size of a stroke order frame, in braille characters.
+const FRAME_COLUMNS: usize = 14;
+const FRAME_ROWS: usize = 7;
+
+#[derive(Args)]
+#[command(group(
+    ArgGroup::new("lookup")
+        .args(["radicals", "skip", "four_corner", "radical", "codepoint", "reference"])
+))]
+pub struct KanjiArgs {
+    /// The kanji to show with its components and codes
+    #[arg(required_unless_present = "lookup", conflicts_with = "lookup")]
+    kanji: Option<String>,
+
+    /// List the kanji containing all the radicals, e.g. `--radicals 氵 木`
+    #[arg(short = 'r', long = "radicals", num_args = 1..)]
+    radicals: Vec<String>,
+
+    /// Find the kanji by SKIP code, e.g. `3-3-1`
+    #[arg(long = "skip", value_name = "CODE")]
+    skip: Option<String>,
+
+    /// Find the kanji by four-corner code, e.g. `6010`
+    #[arg(long = "four-corner", value_name = "CODE")]
+    four_corner: Option<String>,
+
+    /// Find the kanji by classical radical number, e.g. 72
+    #[arg(long = "radical", value_name = "NUMBER")]
+    radical: Option<u16>,
+
+    /// Find the kanji by Unicode or JIS codepoint, e.g. `U+65E5`
+    #[arg(long = "codepoint", value_name = "CODE")]
+    codepoint: Option<String>,
+
+    /// Show the stroke order of the kanji, from the KanjiVG strokes
+    #[arg(short = 's', long = "strokes", requires = "kanji")]
+    strokes: bool,
+
+    /// Write the stroke order of the kanji as an SVG worksheet, named after
+    /// each kanji (e.g. `out-日.svg`) when several are given
+    #[arg(long = "svg", value_name = "FILE", requires = "kanji")]
+    svg: Option<PathBuf>,
+
+    /// Find the kanji by dictionary reference, e.g. `heisig:12`
+    #[arg(long = "reference", value_name = "DICTIONARY:INDEX", value_parser = parse_reference)]
+    reference: Option<(String, String)>,
+}
+
+impl KanjiArgs {
+    fn code(&self) -> Option<KanjiCode> {
+        self.skip
+            .clone()
+            .map(KanjiCode::Skip)
+            .or_else(|| self.four_corner.clone().map(KanjiCode::FourCorner))
+            .or_else(|| self.radical.map(KanjiCode::Radical))
+            .or_else(|| self.codepoint.clone().map(KanjiCode::Codepoint))
+            .or_else(|| {
+                self.reference
+                    .clone()
+                    .map(|(kind, value)| KanjiCode::Reference(kind, value))
+            })
+    }
+}
+
+fn parse_reference(reference: &str) -> Result<(String, String), String> {
+    match reference.split_once(':') {
+        Some((kind, value)) if !kind.is_empty() && !value.is_empty() => {
+            Ok((kind.to_string(), value.to_string()))
+        }
+        _ => Err("expected the dictionary and the index, e.g. heisig:12".into()),
+    }
+}
+
+impl CommandHandler for KanjiArgs {
+    fn handle(&self) -> Result<(), Error> {
+        // the words are not needed to look up the kanji
+        let dictionary_repository = DictionaryRepository::from(WordMap::new(), TagMap::new())
+            .with_kanji(&KANJI_BIN_PATH.as_path())?
+            .with_radicals(&RADICALS_BIN_PATH.as_path())?
+            .with_strokes(&STROKES_BIN_PATH.as_path())?;
+        let controller = KanjiController::new(dictionary_repository);
+        let languages = ConfigRepository::new(CONFIG_PATH.to_path_buf()).languages()?;
+
+        if let Some(text) = &self.kanji {
+            let several = text.chars().count() > 1;
+
+            for literal in text.chars().map(String::from) {
+                let kanji = controller
+                    .kanji(&literal)
+                    .ok_or_else(|| anyhow!("Kanji {} not found", literal))?;
+
+                println!("{}", kanji_entry(kanji, &languages));
+
+                let components = controller.components(&literal);
+
+                if !components.is_empty() {
+                    println!("   Components: {}", components.join(" "));
+                }
+
+                print_codes(kanji);
+                println!();
+
+                if self.strokes || self.svg.is_some() {
+                    let strokes = controller.strokes(&literal);
+
+                    if strokes.is_empty() {
+                        println!("{}\n", MISSING_STROKES);
+                        continue;
+                    }
+
+                    if self.strokes {
+                        print_stroke_order(strokes);
+                    }
+
+                    if let Some(path) = &self.svg {
+                        let path = match several {
+                            true => svg_path(path, &literal),
+                            false => path.clone(),
+                        };
+
+                        fs::write(&path, stroke_order_svg(strokes, FRAMES_PER_ROW))?;
+                        println!("Stroke order of {} written to {:?}\n", literal, path);
+                    }
+                }
+            }
+
+            return Ok(());
+        }
+
+        if let Some(code) = self.code() {
+            let results = controller.kanji_with_code(&code);
+
+            println!("{} kanji found\n", results.len());
+
+            for kanji in results {
+                println!("{}", kanji_entry(kanji, &languages));
+            }
+
+            return Ok(());
+        }
+
+        // the radicals may also be written together, e.g. 氵木
+        let radicals = self
+            .radicals
+            .iter()
+            .flat_map(|radicals| radicals.chars())
+            .map(String::from)
+            .collect::<Vec<String>>();
+        let radicals = radicals.iter().map(String::as_str).collect::<Vec<&str>>();
+
+        let results = controller.kanji_with_radicals(&radicals)?;
+
+        println!("{} kanji with {}\n", results.len(), radicals.join(" "));
+
+        for literal in results {
+            match controller.kanji(literal) {
+                Some(kanji) => println!("{}", kanji_entry(kanji, &languages)),
+                None => println!("{}", literal),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn print_codes(kanji: &Kanji) {
+    let radical =
+        kanji
+            .classical_radical()
+            .map(|number| match kanji.misc.radical_names.is_empty() {
+                true => number.to_string(),
+                false => format!("{} ({})", number, kanji.misc.radical_names.join("、")),
+            });
+    let unicode = kanji
+        .codepoints
+        .iter()
+        .find(|codepoint| codepoint.kind == "ucs")
+        .map(|codepoint| format!("U+{}", codepoint.value.to_uppercase()));
+
+    let codes = [
+        ("Radical", radical),
+        (
+            "SKIP",
+            kanji.query_codes("skip").first().map(|c| c.to_string()),
+        ),
+        (
+            "Four corner",
+            kanji
+                .query_codes("four_corner")
+                .first()
+                .map(|c| c.to_string()),
+        ),
+        ("Unicode", unicode),
+        ("Heisig", kanji.reference("heisig").map(String::from)),
+    ]
+    .into_iter()
+    .filter_map(|(label, code)| code.map(|code| format!("{}: {}", label, code)))
+    .collect::<Vec<String>>();
+
+    if !codes.is_empty() {
+        println!("   {}", codes.join("  "));
+    }
+
+    let variants = kanji
+        .misc
+        .variants
+        .iter()
+        .map(|variant| format!("{} {}", variant.kind, variant.value))
+        .collect::<Vec<String>>();
+
+    if !variants.is_empty() {
+        println!("   Variants: {}", variants.join(", "));
+    }
+}
+
+/// Path of the worksheet of the kanji, e.g. `out-日.svg` for `out.svg`.
+fn svg_path(path: &Path, literal: &str) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+
+    let name = match path.extension() {
+        Some(extension) => format!("{}-{}.{}", stem, literal, extension.to_string_lossy()),
+        None => format!("{}-{}", stem, literal),
+    };
+
+    path.with_file_name(name)
+}
+
+/// Prints a braille frame for each stroke, with the strokes drawn so far.
+fn print_stroke_order(strokes: &[String]) {
+    let mut canvas = BrailleCanvas::new(FRAME_COLUMNS, FRAME_ROWS);
+    let mut frames = Vec::<Vec<String>>::new();
+
+    for path in strokes {
+        canvas.draw_polyline(&flatten_path(path), KANJIVG_SIZE);
+        frames.push(canvas.lines());
+    }
+
+    for (row, chunk) in frames.chunks(FRAMES_PER_ROW).enumerate() {
+        let numbers = (0..chunk.len())
+            .map(|i| {
+                format!(
+                    "{:<width$}",
+                    row * FRAMES_PER_ROW + i + 1,
+                    width = FRAME_COLUMNS
+                )
+            })
+            .collect::<Vec<String>>();
+
+        println!("{}", numbers.join("  ").trim_end());
+
+        for line in 0..FRAME_ROWS {
+            let lines = chunk
+                .iter()
+                .map(|frame| frame[line].as_str())
+                .collect::<Vec<&str>>();
+
+            println!("{}", lines.join("  "));
+        }
+
+        println!();
+    }
+}
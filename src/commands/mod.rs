@@ -1,10 +1,18 @@
+mod analyze;
+mod import;
+mod kanji;
 mod progress;
 mod random;
 mod search;
+mod similar;
 mod study;
 mod study_commands;
 
+pub use analyze::AnalyzeArgs;
+pub use import::ImportArgs;
+pub use kanji::KanjiArgs;
 pub use progress::ProgressArgs;
 pub use random::RandomArgs;
 pub use search::SearchArgs;
+pub use similar::SimilarArgs;
 pub use study::StudyArgs;
@@ -3,8 +3,10 @@ use anyhow::Result;
 use clap::Args;
 use clap::ValueEnum;
 use nika_core::controllers::random_controller::RandomController;
+use nika_core::repositories::config_repository::ConfigRepository;
 use nika_core::repositories::dictionary_repository::DictionaryRepository;
 
+use crate::config::CONFIG_PATH;
 use crate::config::TAGS_BIN_PATH;
 use crate::config::WORDS_BIN_PATH;
 use crate::handlers::CommandHandler;
@@ -31,13 +33,16 @@ impl CommandHandler for RandomArgs {
         let dictionary_repository =
             DictionaryRepository::new(&WORDS_BIN_PATH.as_path(), &TAGS_BIN_PATH.as_path())?;
         let controller = RandomController::new(dictionary_repository);
+        let romaji = ConfigRepository::new(CONFIG_PATH.to_path_buf())
+            .display_settings()?
+            .romaji;
 
         match self.option {
             RandomOption::Word => {
                 let words = controller.random_words(self.count.unwrap_or(1));
 
                 for word in words {
-                    print_word(word, DisplayMode::Short);
+                    print_word(word, &[], DisplayMode::Short, &[], romaji);
                 }
             }
             RandomOption::Kanji => println!("random kanji"),
@@ -2,11 +2,21 @@ use anyhow::Error;
 use anyhow::Result;
 use clap::Args;
 use nika_core::controllers::search_controller::SearchController;
+use nika_core::repositories::config_repository::ConfigRepository;
 use nika_core::repositories::dictionary_repository::DictionaryRepository;
 
+use crate::config::CONFIG_PATH;
+use crate::config::EXAMPLES_BIN_PATH;
+use crate::config::FREQUENCY_BIN_PATH;
+use crate::config::KANJI_BIN_PATH;
 use crate::config::TAGS_BIN_PATH;
 use crate::config::WORDS_BIN_PATH;
 use crate::handlers::CommandHandler;
+use crate::utils::display::print_examples;
+use crate::utils::display::print_kanji_breakdown;
+use crate::utils::display::print_word;
+use crate::utils::display::sense_xrefs;
+use crate::utils::display::DisplayMode;
 
 #[derive(Args)]
 pub struct SearchArgs {
@@ -15,13 +25,28 @@ pub struct SearchArgs {
 
     #[arg(short = 'c', long = "common")]
     common: Option<bool>,
+
+    /// Number of example sentences shown under each word
+    #[arg(
+        short = 'e',
+        long = "examples",
+        num_args = 0..=1,
+        default_missing_value = "3"
+    )]
+    examples: Option<usize>,
 }
 
 impl CommandHandler for SearchArgs {
     fn handle(&self) -> Result<(), Error> {
         let dictionary_repository =
-            DictionaryRepository::new(&WORDS_BIN_PATH.as_path(), &TAGS_BIN_PATH.as_path())?;
+            DictionaryRepository::new(&WORDS_BIN_PATH.as_path(), &TAGS_BIN_PATH.as_path())?
+                .with_frequencies(&FREQUENCY_BIN_PATH.as_path())?
+                .with_examples(&EXAMPLES_BIN_PATH.as_path())?
+                .with_kanji(&KANJI_BIN_PATH.as_path())?;
         let controller = SearchController::new(dictionary_repository);
+        let config_repository = ConfigRepository::new(CONFIG_PATH.to_path_buf());
+        let languages = config_repository.languages()?;
+        let romaji = config_repository.display_settings()?.romaji;
 
         match self.query {
             Some(ref query) => {
@@ -30,7 +55,13 @@ impl CommandHandler for SearchArgs {
                 println!("{} Results found for {}\n", results.len(), query);
 
                 for res in &results {
-                    println!("{:?}\n", res);
+                    let xrefs = sense_xrefs(res, |xref| controller.resolve_xref(xref));
+
+                    print_word(res, &xrefs, DisplayMode::Long, &languages, romaji);
+                    print_kanji_breakdown(&controller.kanji_breakdown(res), &languages);
+
+                    let examples = controller.examples(&res.id);
+                    print_examples(&examples[..self.examples.unwrap_or(0).min(examples.len())]);
                 }
             }
             None => {
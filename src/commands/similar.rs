@@ -0,0 +1,75 @@
+use anyhow::anyhow;
+use anyhow::Error;
+use anyhow::Result;
+use clap::Args;
+use nika_core::controllers::search_controller::SearchController;
+use nika_core::models::jmdict::Word;
+use nika_core::repositories::config_repository::ConfigRepository;
+use nika_core::repositories::dictionary_repository::DictionaryRepository;
+
+use crate::config::CONFIG_PATH;
+use crate::config::FREQUENCY_BIN_PATH;
+use crate::config::TAGS_BIN_PATH;
+use crate::config::WORDS_BIN_PATH;
+use crate::handlers::CommandHandler;
+use crate::utils::display::print_word;
+use crate::utils::display::DisplayMode;
+
+#[derive(Args)]
+pub struct SimilarArgs {
+    /// The word, written in kanji or kana
+    word: String,
+
+    /// Number of words shown for each kind of similarity
+    #[arg(short = 'l', long = "limit", default_value_t = 5)]
+    limit: usize,
+}
+
+impl CommandHandler for SimilarArgs {
+    fn handle(&self) -> Result<(), Error> {
+        let dictionary_repository =
+            DictionaryRepository::new(&WORDS_BIN_PATH.as_path(), &TAGS_BIN_PATH.as_path())?
+                .with_frequencies(&FREQUENCY_BIN_PATH.as_path())?;
+        let controller = SearchController::new(dictionary_repository);
+        let config_repository = ConfigRepository::new(CONFIG_PATH.to_path_buf());
+        let languages = config_repository.languages()?;
+        let romaji = config_repository.display_settings()?.romaji;
+
+        let word = controller
+            .words_with_form(&self.word)
+            .first()
+            .copied()
+            .ok_or_else(|| anyhow!("No word written {}", self.word))?;
+
+        let similar = controller
+            .similar(&word.id)
+            .ok_or_else(|| anyhow!("No word written {}", self.word))?;
+
+        print_word(word, &[], DisplayMode::Long, &languages, romaji);
+
+        for (title, words) in [
+            ("Homophones", &similar.homophones),
+            ("Shared kanji", &similar.shared_kanji),
+            ("Synonyms", &similar.synonyms),
+        ] {
+            print_similar(title, words, self.limit, romaji);
+        }
+
+        Ok(())
+    }
+}
+
+fn print_similar(title: &str, words: &[&Word], limit: usize, romaji: bool) {
+    if words.is_empty() {
+        return;
+    }
+
+    println!("{} ({})\n", title, words.len());
+
+    for word in words.iter().take(limit) {
+        print!("   ");
+        print_word(word, &[], DisplayMode::Short, &[], romaji);
+    }
+
+    println!();
+}
@@ -9,15 +9,18 @@ use nika_core::repositories::list_repository::ListRepository;
 
 use super::study_commands::AddArgs;
 use super::study_commands::DailyArgs;
+use super::study_commands::ExportArgs;
 use super::study_commands::ListArgs;
 use super::study_commands::MarkArgs;
 use super::study_commands::RemoveArgs;
 use super::study_commands::SelectArgs;
 use super::study_commands::SetArgs;
 use super::study_commands::ShowArgs;
-use crate::config::app_config_dir;
 use crate::config::app_data_dir;
-use crate::config::CONFIG_NAME;
+use crate::config::CONFIG_PATH;
+use crate::config::EXAMPLES_BIN_PATH;
+use crate::config::FREQUENCY_BIN_PATH;
+use crate::config::KANJI_BIN_PATH;
 use crate::config::TAGS_BIN_PATH;
 use crate::config::WORDS_BIN_PATH;
 use crate::handlers::CommandHandler;
@@ -33,6 +36,7 @@ pub enum StudyCommands {
     Mark(MarkArgs),
     Show(ShowArgs),
     Set(SetArgs),
+    Export(ExportArgs),
 }
 
 #[derive(Args)]
@@ -44,11 +48,13 @@ pub struct StudyArgs {
 impl CommandHandler for StudyArgs {
     fn handle(&self) -> Result<(), Error> {
         let lists_path = app_data_dir().join("lists");
-        let config_path = app_config_dir().join(format!("{}.toml", CONFIG_NAME));
 
         let dictionary_repository =
-            DictionaryRepository::new(&WORDS_BIN_PATH.as_path(), &TAGS_BIN_PATH.as_path())?;
-        let config_repository = ConfigRepository::new(config_path);
+            DictionaryRepository::new(&WORDS_BIN_PATH.as_path(), &TAGS_BIN_PATH.as_path())?
+                .with_frequencies(&FREQUENCY_BIN_PATH.as_path())?
+                .with_examples(&EXAMPLES_BIN_PATH.as_path())?
+                .with_kanji(&KANJI_BIN_PATH.as_path())?;
+        let config_repository = ConfigRepository::new(CONFIG_PATH.to_path_buf());
         let list_repository = ListRepository::new(lists_path);
 
         let study_controller =
@@ -63,6 +69,7 @@ impl CommandHandler for StudyArgs {
             StudyCommands::List(args) => args.handle(&study_controller),
             StudyCommands::Show(args) => args.handle(&study_controller),
             StudyCommands::Set(args) => args.handle(&study_controller),
+            StudyCommands::Export(args) => args.handle(&study_controller),
         }
     }
 }
@@ -1,20 +1,132 @@
 use std::fs;
+use std::ops::Range;
 use std::path::PathBuf;
 
+use anyhow::anyhow;
 use anyhow::Error;
 use anyhow::Result;
 use clap::Args;
+use clap::ValueEnum;
 use nika_core::controllers::study_controller::StudyController;
 use nika_core::errors::NikaError;
 use nika_core::errors::StudyListError;
+use nika_core::models::study_list::ItemKind;
 use nika_core::models::study_list::StudyList;
 
 use crate::handlers::StudyCommandHandler;
+use crate::messages::MISSING_FREQUENCIES;
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Preset {
+    Frequency,
+    /// Kanji of a school grade, from Kanjidic
+    KanjiGrade,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ListKind {
+    Word,
+    Kanji,
+}
 
 #[derive(Args)]
 pub struct AddArgs {
     name: String,
-    file: PathBuf,
+    /// File with a word id (or a kanji for the kanji lists) per line
+    #[arg(required_unless_present = "preset")]
+    file: Option<PathBuf>,
+    /// Kind of the items of the file
+    #[arg(
+        short = 'k',
+        long = "kind",
+        value_enum,
+        default_value = "word",
+        conflicts_with = "preset"
+    )]
+    kind: ListKind,
+    /// Create the list from the bundled words instead of a file
+    #[arg(short = 'p', long = "preset", value_enum, conflicts_with = "file")]
+    preset: Option<Preset>,
+    /// Frequency ranks of the words of the frequency preset, the end being
+    /// excluded (e.g. 1000..2000 for the ranks 1000 to 1999)
+    #[arg(
+        short = 'r',
+        long = "range",
+        value_parser = parse_range,
+        required_if_eq("preset", "frequency")
+    )]
+    range: Option<Range<u32>>,
+    /// School grade of the kanji of the kanji-grade preset, 1 to 6 or 8 for
+    /// the other Jōyō kanji
+    #[arg(short = 'g', long = "grade", required_if_eq("preset", "kanji-grade"))]
+    grade: Option<u16>,
+}
+
+impl AddArgs {
+    fn items(&self, controller: &StudyController) -> Result<Vec<String>, Error> {
+        let Some(preset) = self.preset else {
+            let file = self.file.as_ref().ok_or_else(|| anyhow!("Missing file"))?;
+
+            return Ok(fs::read_to_string(file)?
+                .lines()
+                .map(String::from)
+                .collect());
+        };
+
+        match preset {
+            Preset::KanjiGrade => {
+                let grade = self.grade.ok_or_else(|| anyhow!("Missing grade"))?;
+                let items = controller.grade_items(grade);
+
+                if items.is_empty() {
+                    return Err(anyhow!("No kanji of grade {}", grade));
+                }
+
+                Ok(items)
+            }
+            Preset::Frequency => {
+                if !controller.has_frequencies() {
+                    return Err(anyhow!(MISSING_FREQUENCIES));
+                }
+
+                let range = self.range.clone().unwrap_or(1..u32::MAX);
+                Ok(controller.frequency_items(range))
+            }
+        }
+    }
+
+    fn kind(&self) -> ItemKind {
+        match (self.preset, self.kind) {
+            (Some(Preset::KanjiGrade), _) | (None, ListKind::Kanji) => ItemKind::Kanji,
+            _ => ItemKind::Word,
+        }
+    }
+}
+
+/// Parses a range of ranks such as `1000..2000`, `..500` or `1000..`, the end
+/// being excluded.
+fn parse_range(s: &str) -> Result<Range<u32>, String> {
+    let (start, end) = s
+        .split_once("..")
+        .ok_or_else(|| format!("Invalid range '{}', expected e.g. 1000..2000", s))?;
+
+    let bound = |bound: &str, default: u32| match bound.trim() {
+        "" => Ok(default),
+        bound => bound
+            .parse::<u32>()
+            .map_err(|_| format!("Invalid rank '{}'", bound)),
+    };
+
+    let range = bound(start, 1)?..bound(end, u32::MAX)?;
+
+    if range.is_empty() {
+        return Err(format!(
+            "Empty range '{}', the end is excluded and must be greater than the start",
+            s
+        ));
+    }
+
+    Ok(range)
 }
 
 impl StudyCommandHandler for AddArgs {
@@ -22,12 +134,8 @@ impl StudyCommandHandler for AddArgs {
         // TODO: ask for overwrite when the name is already present!
         // TODO: allow to pass the default value for the number of words per day
 
-        let items = fs::read_to_string(&self.file)?
-            .lines()
-            .map(String::from)
-            .collect::<Vec<String>>();
-
-        let study_list = StudyList::new(&self.name, items);
+        let items = self.items(controller)?;
+        let study_list = StudyList::new(&self.name, items).with_kind(self.kind());
 
         let is_empty = controller.lists()?.is_empty();
 
@@ -54,3 +162,19 @@ impl StudyCommandHandler for AddArgs {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_range() {
+        assert_eq!(parse_range("1000..2000"), Ok(1000..2000));
+        assert_eq!(parse_range("..500"), Ok(1..500));
+        assert_eq!(parse_range("1000.."), Ok(1000..u32::MAX));
+        assert!(parse_range("1000").is_err());
+        assert!(parse_range("a..b").is_err());
+        assert!(parse_range("2000..1000").is_err());
+        assert!(parse_range("5..5").is_err());
+    }
+}
@@ -3,14 +3,26 @@ use anyhow::Ok;
 use anyhow::Result;
 use clap::Args;
 use nika_core::controllers::study_controller::StudyController;
-use nika_core::models::jmdict::Kana;
-use nika_core::models::jmdict::Kanji;
-use nika_core::models::jmdict::Sense;
+use nika_core::importer::tatoeba::Example;
+use nika_core::models::jmdict::Language3Letter;
 use nika_core::models::jmdict::Word;
+use nika_core::models::kanjidic;
 use nika_core::models::link::Link;
+use nika_core::models::study_list::ItemKind;
 
 use crate::commands::study_commands::utils::get_list_name;
 use crate::handlers::StudyCommandHandler;
+use crate::utils::display;
+use crate::utils::display::kanji_entry;
+use crate::utils::display::print_examples;
+use crate::utils::display::print_kanji;
+use crate::utils::display::print_kanji_breakdown;
+use crate::utils::display::print_pitch_accents;
+use crate::utils::display::print_ruby;
+use crate::utils::display::print_senses;
+use crate::utils::display::reading;
+use crate::utils::display::sense_xrefs;
+use crate::utils::display::SenseXrefs;
 use crate::utils::links::generate_hyperlink;
 
 #[derive(Args)]
@@ -20,6 +32,17 @@ pub struct DailyArgs {
     /// Show only the words
     #[arg(short = 's', long = "summary")]
     summary: bool,
+    /// Number of example sentences shown under each word
+    #[arg(
+        short = 'e',
+        long = "examples",
+        num_args = 0..=1,
+        default_missing_value = "3"
+    )]
+    examples: Option<usize>,
+    /// Show the words read the same way but written with other kanji
+    #[arg(long = "homophones")]
+    homophones: bool,
 }
 
 impl StudyCommandHandler for DailyArgs {
@@ -27,14 +50,20 @@ impl StudyCommandHandler for DailyArgs {
         let list_name =
             get_list_name(self.name.as_deref(), controller.selected_list()?.as_deref())?;
 
+        if controller.list(&list_name)?.kind == ItemKind::Kanji {
+            return print_daily_kanji(controller, &list_name, self.summary);
+        }
+
         let words = controller.study_words(&list_name, true)?;
         let links = controller.get_links()?;
+        let languages = controller.languages()?;
+        let romaji = controller.display_settings()?.romaji;
 
         if self.summary {
             println!("NIKA • Today's Summary:\n");
 
             words.iter().enumerate().for_each(|(index, x)| {
-                let entry = summary_word_entry(x, &links);
+                let entry = summary_word_entry(x, &links, romaji);
                 println!("{}. {}", index + 1, entry);
             });
             println!();
@@ -49,13 +78,54 @@ impl StudyCommandHandler for DailyArgs {
         // links to online dictionaries
 
         for (i, word) in words.iter().enumerate() {
-            detailed_print(i + 1, word, &links);
+            let examples = controller.examples(&word.id);
+            let examples = &examples[..self.examples.unwrap_or(0).min(examples.len())];
+
+            let homophones = match self.homophones {
+                true => controller.homophones(&word.id),
+                false => vec![],
+            };
+
+            let details = WordDetails {
+                examples,
+                homophones,
+                kanji: controller.kanji_breakdown(word),
+                xrefs: sense_xrefs(word, |xref| controller.resolve_xref(xref)),
+            };
+
+            detailed_print(i + 1, word, &links, &languages, romaji, &details);
         }
 
         Ok(())
     }
 }
 
+fn print_daily_kanji(controller: &StudyController, name: &str, summary: bool) -> Result<()> {
+    let kanji = controller.study_kanji(name, true)?;
+    let links = controller.get_links()?;
+    let languages = controller.languages()?;
+
+    if summary {
+        println!("NIKA • Today's Kanji:\n");
+
+        for (i, k) in kanji.iter().enumerate() {
+            println!("{}. {}", i + 1, kanji_entry(k, &languages));
+        }
+        println!();
+
+        return Ok(());
+    }
+
+    println!("Japanese Daily Kanji\n");
+
+    for (i, k) in kanji.iter().enumerate() {
+        print_kanji(&format!("{}. ", i + 1), k, &languages);
+        println!("   {}\n\n", print_links(&k.literal, &links));
+    }
+
+    Ok(())
+}
+
 fn print_links(text: &str, links: &[Link]) -> String {
     let hyperlinks = links
         .iter()
@@ -69,66 +139,44 @@ fn print_links(text: &str, links: &[Link]) -> String {
     hyperlinks
 }
 
-fn print_sense(senses: &[Sense]) {
-    let text = senses
-        .iter()
-        .enumerate()
-        .map(|(i, s)| {
-            format!(
-                "   {}\n{}. {}",
-                s.part_of_speech
-                    //.iter()
-                    //.map(|tag| dictionary().tag(tag).unwrap_or(tag).clone())
-                    //.collect::<Vec<String>>()
-                    .join(", "),
-                i + 1,
-                s.gloss
-                    .iter()
-                    .map(|g| g.text.clone())
-                    .collect::<Vec<String>>()
-                    .join(", ")
-            )
-        })
-        .collect::<Vec<String>>()
-        .join("\n");
-
-    for line in text.lines() {
-        println!("   {}", line);
-    }
-
-    println!();
+/// Optional information shown under the senses of a word.
+struct WordDetails<'a> {
+    examples: &'a [Example],
+    homophones: Vec<&'a Word>,
+    kanji: Vec<&'a kanjidic::Kanji>,
+    xrefs: Vec<SenseXrefs<'a>>,
 }
 
-fn detailed_print(n: usize, word: &Word, links: &[Link]) {
+fn detailed_print(
+    n: usize,
+    word: &Word,
+    links: &[Link],
+    languages: &[Language3Letter],
+    romaji: bool,
+    details: &WordDetails,
+) {
     // if let Some((first, others)) = word.kanji.split_first() {
     //     println!("{}", first.text);
     // }
 
-    let mut kanji_kana_map = Vec::<(&Kanji, &Kana)>::new();
-
-    for kana in &word.kana {
-        for a in &kana.applies_to_kanji {
-            if a == "*" {
-                for kanji in &word.kanji {
-                    kanji_kana_map.push((kanji, kana));
-                }
-            } else if let Some(kanji) = &word.kanji.iter().find(|k| k.text == *a) {
-                kanji_kana_map.push((*kanji, kana));
-            }
-        }
-    }
+    let kanji_kana_map = word.kanji_kana_pairs();
 
     if let Some((first, others)) = kanji_kana_map.split_first() {
-        println!("{}. {} 「{}」\n", n, first.0.text, first.1.text);
+        print_ruby(&format!("{}. ", n), &first.0.text, &first.1.text, romaji);
+        println!();
 
-        print_sense(&word.sense);
+        print_pitch_accents(&word.kana);
+        print_senses(&word.sense, &details.xrefs, languages);
+        print_kanji_breakdown(&details.kanji, languages);
+        print_examples(details.examples);
+        print_homophones(&details.homophones);
 
         if !others.is_empty() {
             println!(
                 "   Other forms:\n   {}\n",
                 others
                     .iter()
-                    .map(|l| format!("{} 【{}】", l.0.text, l.1.text))
+                    .map(|l| format!("{} 【{}】", l.0.text, reading(&l.1.text, romaji)))
                     .collect::<Vec<String>>()
                     .join("、")
             );
@@ -138,13 +186,34 @@ fn detailed_print(n: usize, word: &Word, links: &[Link]) {
     } else {
         let text = &word.kana.first().unwrap().text;
 
-        println!("{}. {}\n", n, text);
-        print_sense(&word.sense);
+        println!("{}. {}\n", n, reading(text, romaji));
+        print_pitch_accents(&word.kana);
+        print_senses(&word.sense, &details.xrefs, languages);
+        print_kanji_breakdown(&details.kanji, languages);
+        print_examples(details.examples);
+        print_homophones(&details.homophones);
         println!("   {}\n\n", print_links(text, links));
     }
 }
 
-fn summary_word_entry(word: &Word, links: &[Link]) -> String {
+fn print_homophones(homophones: &[&Word]) {
+    if homophones.is_empty() {
+        return;
+    }
+
+    println!(
+        "   Homophones: {}\n",
+        homophones
+            .iter()
+            .filter_map(|word| word.kanji.first())
+            .take(5)
+            .map(|kanji| kanji.text.as_str())
+            .collect::<Vec<&str>>()
+            .join("、")
+    );
+}
+
+fn summary_word_entry(word: &Word, links: &[Link], romaji: bool) -> String {
     let text: String;
     let reading: Option<String>;
 
@@ -156,10 +225,9 @@ fn summary_word_entry(word: &Word, links: &[Link]) -> String {
         reading = None;
     }
 
-    let word_fmt = if reading.is_none() {
-        text.clone()
-    } else {
-        format!("{} ({})", text, reading.unwrap())
+    let word_fmt = match reading {
+        Some(kana) => format!("{} ({})", text, display::reading(&kana, romaji)),
+        None => display::reading(&text, romaji),
     };
 
     let hyperlinks = links
@@ -0,0 +1,98 @@
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::Error;
+use anyhow::Result;
+use clap::Args;
+use nika_core::controllers::study_controller::StudyController;
+use nika_core::models::jmdict::Language3Letter;
+use nika_core::models::jmdict::Word;
+use nika_core::models::kanjidic::Kanji;
+use nika_core::models::study_list::ItemKind;
+use nika_core::utils::furigana;
+
+use super::utils::get_list_name;
+use crate::handlers::StudyCommandHandler;
+
+/// Export the words of a study list as Anki notes, with the furigana of the
+/// word (or the kanji and its readings) and its meanings separated by a tab.
+#[derive(Args)]
+pub struct ExportArgs {
+    #[arg(short = 'n', long = "name")]
+    name: Option<String>,
+
+    /// File to write the notes to, instead of the standard output
+    #[arg(short = 'o', long = "output")]
+    output: Option<PathBuf>,
+}
+
+impl StudyCommandHandler for ExportArgs {
+    fn handle(&self, controller: &StudyController) -> Result<(), Error> {
+        let list_name =
+            get_list_name(self.name.as_deref(), controller.selected_list()?.as_deref())?;
+
+        let languages = controller.languages()?;
+
+        let notes = match controller.list(&list_name)?.kind {
+            ItemKind::Word => controller
+                .study_words(&list_name, false)?
+                .iter()
+                .map(|word| anki_note(word, &languages))
+                .collect::<Vec<String>>(),
+            ItemKind::Kanji => controller
+                .study_kanji(&list_name, false)?
+                .iter()
+                .map(|kanji| anki_kanji_note(kanji, &languages))
+                .collect::<Vec<String>>(),
+        }
+        .join("\n");
+
+        match &self.output {
+            Some(path) => fs::write(path, notes + "\n")?,
+            None => println!("{}", notes),
+        }
+
+        Ok(())
+    }
+}
+
+fn anki_note(word: &Word, languages: &[Language3Letter]) -> String {
+    let front = match word.kanji_kana_pairs().first() {
+        Some((kanji, kana)) => furigana::to_anki(&furigana::align(&kanji.text, &kana.text)),
+        None => word
+            .kana
+            .first()
+            .map(|k| k.text.clone())
+            .unwrap_or_default(),
+    };
+
+    let back = word
+        .sense
+        .iter()
+        .map(|sense| {
+            sense
+                .glosses(languages)
+                .iter()
+                .map(|g| g.text.as_str())
+                .collect::<Vec<&str>>()
+                .join(", ")
+        })
+        .filter(|glosses| !glosses.is_empty())
+        .collect::<Vec<String>>()
+        .join("; ");
+
+    format!("{}\t{}", front, back)
+}
+
+fn anki_kanji_note(kanji: &Kanji, languages: &[Language3Letter]) -> String {
+    let readings = [kanji.on_readings(), kanji.kun_readings()]
+        .concat()
+        .join("、");
+
+    format!(
+        "{}\t{}<br>{}",
+        kanji.literal,
+        readings,
+        kanji.meanings(languages).join(", ")
+    )
+}
@@ -2,6 +2,7 @@ use anyhow::Error;
 use anyhow::Result;
 use clap::Args;
 use nika_core::controllers::study_controller::StudyController;
+use nika_core::models::study_list::ItemKind;
 
 use crate::handlers::StudyCommandHandler;
 use crate::messages::EMPTY_STUDY_LISTS;
@@ -26,6 +27,10 @@ impl StudyCommandHandler for ListArgs {
         for (i, item) in lists.iter().enumerate() {
             let mut fmt_item = format!("{}. {}", i, item.name);
 
+            if item.kind == ItemKind::Kanji {
+                fmt_item = format!("{} [kanji]", fmt_item);
+            }
+
             if controller.selected_list()?.is_some_and(|c| c == item.name) {
                 fmt_item = format!("{} (selected)", fmt_item);
             }
@@ -1,19 +1,51 @@
 use anyhow::Error;
 use anyhow::Result;
 use clap::Args;
+use clap::ValueEnum;
 use nika_core::controllers::study_controller::StudyController;
+use nika_core::models::study_list::ItemStatus;
 
+use super::utils::get_list_name;
 use crate::handlers::StudyCommandHandler;
 
+#[derive(Clone, Copy, ValueEnum)]
+pub enum MarkStatus {
+    New,
+    Learning,
+    Known,
+    Skipped,
+}
+
+impl MarkStatus {
+    fn item_status(self) -> Option<ItemStatus> {
+        match self {
+            Self::New => None,
+            Self::Learning => Some(ItemStatus::Learning),
+            Self::Known => Some(ItemStatus::Known),
+            Self::Skipped => Some(ItemStatus::Skipped),
+        }
+    }
+}
+
 #[derive(Args)]
 pub struct MarkArgs {
+    /// The word to mark, by id or by any of its forms, or the kanji
     pub word: String,
-    pub status: String,
+    #[arg(value_enum)]
+    pub status: MarkStatus,
+    #[arg(short = 'n', long = "name")]
+    name: Option<String>,
 }
 
 impl StudyCommandHandler for MarkArgs {
-    fn handle(&self, _controller: &StudyController) -> Result<(), Error> {
-        println!("{:?} marked as {:?}", self.word, self.status);
+    fn handle(&self, controller: &StudyController) -> Result<(), Error> {
+        let list_name =
+            get_list_name(self.name.as_deref(), controller.selected_list()?.as_deref())?;
+
+        let id = controller.mark(&list_name, &self.word, self.status.item_status())?;
+        let status = self.status.to_possible_value().unwrap();
+
+        println!("{} ({}) marked as {}", self.word, id, status.get_name());
         Ok(())
     }
 }
@@ -1,5 +1,6 @@
 mod add;
 mod daily;
+mod export;
 mod list;
 mod mark;
 mod remove;
@@ -10,6 +11,7 @@ mod utils;
 
 pub use add::AddArgs;
 pub use daily::DailyArgs;
+pub use export::ExportArgs;
 pub use list::ListArgs;
 pub use mark::MarkArgs;
 pub use remove::RemoveArgs;
@@ -2,9 +2,11 @@ use anyhow::Error;
 use anyhow::Result;
 use clap::Args;
 use nika_core::controllers::study_controller::StudyController;
+use nika_core::models::study_list::ItemKind;
 
 use super::utils::get_list_name;
 use crate::handlers::StudyCommandHandler;
+use crate::utils::display::kanji_entry;
 
 #[derive(Args)]
 pub struct ShowArgs {
@@ -17,6 +19,16 @@ impl StudyCommandHandler for ShowArgs {
         let list_name =
             get_list_name(self.name.as_deref(), controller.selected_list()?.as_deref())?;
 
+        if controller.list(&list_name)?.kind == ItemKind::Kanji {
+            let languages = controller.languages()?;
+
+            for kanji in controller.study_kanji(&list_name, false)? {
+                println!("{}", kanji_entry(kanji, &languages));
+            }
+
+            return Ok(());
+        }
+
         let words = controller.study_words(&list_name, false)?;
 
         // TODO: filter (by status: skipped, done, ...)
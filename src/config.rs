@@ -10,6 +10,15 @@ pub const CONFIG_NAME: &str = "config";
 pub static WORDS_BIN_PATH: Lazy<PathBuf> = Lazy::new(|| app_data_dir().join("jmdict-words.bin"));
 pub static TAGS_BIN_PATH: Lazy<PathBuf> = Lazy::new(|| app_data_dir().join("jmdict-tags.bin"));
 pub static KANJI_BIN_PATH: Lazy<PathBuf> = Lazy::new(|| app_data_dir().join("kanjidic.bin"));
+pub static RADICALS_BIN_PATH: Lazy<PathBuf> = Lazy::new(|| app_data_dir().join("radicals.bin"));
+pub static STROKES_BIN_PATH: Lazy<PathBuf> = Lazy::new(|| app_data_dir().join("strokes.bin"));
+pub static FREQUENCY_BIN_PATH: Lazy<PathBuf> = Lazy::new(|| app_data_dir().join("frequency.bin"));
+pub static FREQUENCY_LIST_PATH: Lazy<PathBuf> = Lazy::new(|| app_data_dir().join("frequency.tsv"));
+pub static ACCENTS_LIST_PATH: Lazy<PathBuf> = Lazy::new(|| app_data_dir().join("accents.tsv"));
+pub static EXAMPLES_BIN_PATH: Lazy<PathBuf> = Lazy::new(|| app_data_dir().join("examples.bin"));
+pub static SENSES_PATH: Lazy<PathBuf> = Lazy::new(|| app_data_dir().join("senses.json"));
+pub static CONFIG_PATH: Lazy<PathBuf> =
+    Lazy::new(|| app_config_dir().join(format!("{}.toml", CONFIG_NAME)));
 
 pub const DICTIONARY_RELEASE_URL: &str =
     "https://api.github.com/repos/scriptin/jmdict-simplified/releases/latest";
@@ -3,4 +3,5 @@ pub mod commands;
 pub mod config;
 pub mod handlers;
 pub mod messages;
+pub mod schedule;
 mod utils;
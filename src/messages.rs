@@ -2,3 +2,16 @@ pub const EMPTY_STUDY_LISTS: &str =
     "No lists found. Add a list using the following command:\n$ nika study add <NAME> <FILE>";
 
 pub const NO_LIST_SELECTED:&str = "No list selected. Select the study list using the following command:\n$ nika study select <NAME>";
+
+pub const OUTDATED_DICTIONARY: &str =
+    "The dictionary is outdated. Update it using the following command:\n$ nika-updater";
+
+pub const MISSING_SENSES: &str =
+    "Context words not found. Generate them using the following command:\n$ nika-updater";
+
+pub const OUTDATED_SENSES: &str =
+    "Context words are outdated. Generate them using the following command:\n$ nika-updater";
+
+pub const MISSING_FREQUENCIES: &str = "Frequency ranks not found. Generate them from a frequency list (surface, reading and rank separated by tabs) using the following command:\n$ nika-updater --frequency <FILE>";
+
+pub const MISSING_STROKES: &str = "Stroke order not found. Import it from a KanjiVG archive (the kanjivg XML file or a directory of SVG files) using the following command:\n$ nika-updater --kanjivg <PATH>";
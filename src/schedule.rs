@@ -0,0 +1,268 @@
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
+use std::process::Stdio;
+
+use anyhow::anyhow;
+use anyhow::Context;
+use anyhow::Error;
+use anyhow::Result;
+use clap::ValueEnum;
+
+const UPDATER_NAME: &str = "nika-updater";
+const CRON_MARKER: &str = "# nika-updater";
+/// Longest interval of the cron entry, whose day-of-month step restarts every
+/// month.
+const MAX_CRON_INTERVAL_DAYS: u64 = 31;
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Scheduler {
+    Cron,
+    Systemd,
+}
+
+/// Interval of the scheduled updates for the configured interval: at least a
+/// day, and at most a month with cron.
+pub fn effective_interval(scheduler: Scheduler, interval_days: u64) -> u64 {
+    match scheduler {
+        Scheduler::Cron => interval_days.clamp(1, MAX_CRON_INTERVAL_DAYS),
+        Scheduler::Systemd => interval_days.max(1),
+    }
+}
+
+pub fn install(scheduler: Scheduler, updater: &Path, interval_days: u64) -> Result<(), Error> {
+    let interval_days = effective_interval(scheduler, interval_days);
+
+    match scheduler {
+        Scheduler::Cron => {
+            let crontab =
+                with_cron_entry(&read_crontab()?, Some(&cron_entry(updater, interval_days)));
+            write_crontab(&crontab)
+        }
+        Scheduler::Systemd => {
+            let (service_path, timer_path) = systemd_unit_paths()?;
+            fs::create_dir_all(service_path.parent().unwrap_or(Path::new(".")))?;
+
+            fs::write(&service_path, systemd_service(updater))?;
+            fs::write(&timer_path, systemd_timer(interval_days))?;
+
+            systemctl(&["daemon-reload"])?;
+            systemctl(&["enable", "--now", &format!("{}.timer", UPDATER_NAME)])
+        }
+    }
+}
+
+pub fn remove(scheduler: Scheduler) -> Result<(), Error> {
+    match scheduler {
+        Scheduler::Cron => write_crontab(&with_cron_entry(&read_crontab()?, None)),
+        Scheduler::Systemd => {
+            let (service_path, timer_path) = systemd_unit_paths()?;
+
+            if !timer_path.exists() && !service_path.exists() {
+                return Ok(());
+            }
+
+            // the timer may be already disabled
+            systemctl(&["disable", "--now", &format!("{}.timer", UPDATER_NAME)]).ok();
+
+            for path in [timer_path, service_path] {
+                if path.exists() {
+                    fs::remove_file(path)?;
+                }
+            }
+
+            systemctl(&["daemon-reload"])
+        }
+    }
+}
+
+/// Returns a description of the scheduled update, if any.
+pub fn status(scheduler: Scheduler) -> Result<Option<String>, Error> {
+    match scheduler {
+        Scheduler::Cron => Ok(read_crontab()?
+            .lines()
+            .find(|line| is_updater_entry(line))
+            .map(String::from)),
+        Scheduler::Systemd => {
+            let (_, timer_path) = systemd_unit_paths()?;
+
+            if !timer_path.exists() {
+                return Ok(None);
+            }
+
+            let output = Command::new("systemctl")
+                .args(["--user", "is-active", &format!("{}.timer", UPDATER_NAME)])
+                .output()
+                .with_context(|| "Failed to run systemctl")?;
+
+            Ok(Some(format!(
+                "{} ({})",
+                timer_path.display(),
+                String::from_utf8_lossy(&output.stdout).trim()
+            )))
+        }
+    }
+}
+
+/// Entry running the updater every `interval_days` days of the month, the
+/// step restarting on the 1st of each month (e.g. on the 1st, 11th, 21st and
+/// 31st for 10 days).
+fn cron_entry(updater: &Path, interval_days: u64) -> String {
+    format!(
+        "0 12 */{} * * {} {}",
+        interval_days,
+        updater.display(),
+        CRON_MARKER
+    )
+}
+
+/// Entries written by the previous installer have no marker, so any line
+/// running the updater is considered ours.
+fn is_updater_entry(line: &str) -> bool {
+    let line = line.trim_start();
+    !line.starts_with('#') && (line.ends_with(CRON_MARKER) || line.ends_with(UPDATER_NAME))
+}
+
+/// Replaces the updater entries of the crontab with the given entry, or
+/// removes them when no entry is given.
+fn with_cron_entry(crontab: &str, entry: Option<&str>) -> String {
+    let mut lines = crontab
+        .lines()
+        .filter(|line| !is_updater_entry(line))
+        .collect::<Vec<&str>>();
+
+    if let Some(entry) = entry {
+        lines.push(entry);
+    }
+
+    lines.iter().map(|line| format!("{}\n", line)).collect()
+}
+
+fn read_crontab() -> Result<String, Error> {
+    let output = Command::new("crontab")
+        .arg("-l")
+        .output()
+        .with_context(|| "Failed to run crontab")?;
+
+    // crontab exits with an error when the user has no crontab yet
+    if !output.status.success() {
+        return Ok(String::new());
+    }
+
+    Ok(String::from_utf8(output.stdout)?)
+}
+
+fn write_crontab(content: &str) -> Result<(), Error> {
+    let mut child = Command::new("crontab")
+        .arg("-")
+        .stdin(Stdio::piped())
+        .spawn()
+        .with_context(|| "Failed to run crontab")?;
+
+    child
+        .stdin
+        .take()
+        .ok_or_else(|| anyhow!("Failed to open crontab stdin"))?
+        .write_all(content.as_bytes())?;
+
+    if !child.wait()?.success() {
+        return Err(anyhow!("Failed to write the crontab"));
+    }
+
+    Ok(())
+}
+
+fn systemd_unit_paths() -> Result<(PathBuf, PathBuf), Error> {
+    let dir = dirs::config_dir()
+        .ok_or_else(|| anyhow!("Could not determine config directory."))?
+        .join("systemd")
+        .join("user");
+
+    Ok((
+        dir.join(format!("{}.service", UPDATER_NAME)),
+        dir.join(format!("{}.timer", UPDATER_NAME)),
+    ))
+}
+
+fn systemd_service(updater: &Path) -> String {
+    format!(
+        "[Unit]\nDescription=Update the Nika dictionary\n\n[Service]\nType=oneshot\nExecStart={}\n",
+        updater.display()
+    )
+}
+
+fn systemd_timer(interval_days: u64) -> String {
+    format!(
+        "[Unit]\nDescription=Update the Nika dictionary every {} days\n\n[Timer]\nOnBootSec=15min\nOnUnitActiveSec={}d\nPersistent=true\n\n[Install]\nWantedBy=timers.target\n",
+        interval_days, interval_days
+    )
+}
+
+fn systemctl(args: &[&str]) -> Result<(), Error> {
+    let status = Command::new("systemctl")
+        .arg("--user")
+        .args(args)
+        .status()
+        .with_context(|| "Failed to run systemctl")?;
+
+    if !status.success() {
+        return Err(anyhow!("systemctl --user {} failed", args.join(" ")));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ENTRY: &str = "0 12 */3 * * /bin/nika-updater # nika-updater";
+
+    #[test]
+    fn test_install_is_idempotent() {
+        let crontab = "0 0 * * * backup\n";
+
+        let once = with_cron_entry(crontab, Some(ENTRY));
+        let twice = with_cron_entry(&once, Some(ENTRY));
+
+        assert_eq!(once, format!("{}{}\n", crontab, ENTRY));
+        assert_eq!(once, twice);
+    }
+
+    #[test]
+    fn test_replace_legacy_entries() {
+        let crontab = "0 12 */3 * * /bin/nika-updater\n0 12 */3 * * /bin/nika-updater\n";
+
+        assert_eq!(
+            with_cron_entry(crontab, Some(ENTRY)),
+            format!("{}\n", ENTRY)
+        );
+    }
+
+    #[test]
+    fn test_remove_keeps_other_entries() {
+        let crontab = format!("# 0 12 * * * nika-updater\n{}\n0 0 * * * backup\n", ENTRY);
+
+        assert_eq!(
+            with_cron_entry(&crontab, None),
+            "# 0 12 * * * nika-updater\n0 0 * * * backup\n"
+        );
+    }
+
+    #[test]
+    fn test_cron_entry() {
+        let entry = cron_entry(Path::new("/bin/nika-updater"), 3);
+
+        assert_eq!(entry, ENTRY);
+        assert!(is_updater_entry(&entry));
+    }
+
+    #[test]
+    fn test_effective_interval() {
+        assert_eq!(effective_interval(Scheduler::Cron, 0), 1);
+        assert_eq!(effective_interval(Scheduler::Cron, 60), 31);
+        assert_eq!(effective_interval(Scheduler::Systemd, 60), 60);
+    }
+}
@@ -1,22 +1,369 @@
+use nika_core::importer::tatoeba::Example;
+use nika_core::models::jmdict::Kana;
+use nika_core::models::jmdict::Language3Letter;
+use nika_core::models::jmdict::Sense;
 use nika_core::models::jmdict::Word;
+use nika_core::models::jmdict::Xref;
+use nika_core::models::kanjidic::Kanji;
+use nika_core::utils::furigana;
+use nika_core::utils::furigana::Segment;
+use nika_core::utils::japanese::JapaneseCharacter;
+use nika_core::utils::japanese::JapaneseString;
 
+#[derive(Default)]
 pub enum DisplayMode {
     Short,
+    #[default]
     Long,
 }
 
-impl Default for DisplayMode {
-    fn default() -> Self {
-        Self::Long
-    }
-}
-
-pub fn print_word(word: &Word, mode: DisplayMode) {
+pub fn print_word(
+    word: &Word,
+    xrefs: &[SenseXrefs],
+    mode: DisplayMode,
+    languages: &[Language3Letter],
+    romaji: bool,
+) {
     let kanji = word.kanji.first().map(|k| k.text.as_str()).unwrap_or("");
     let kana = word.kana.first().map(|k| k.text.as_str()).unwrap_or("");
 
     match mode {
-        DisplayMode::Short => println!("{} {}", kanji, kana),
-        DisplayMode::Long => todo!(),
+        DisplayMode::Short => println!("{} {}", kanji, reading(kana, romaji)),
+        DisplayMode::Long => {
+            if kanji.is_empty() {
+                println!("{}\n", reading(kana, romaji));
+            } else {
+                print_ruby("", kanji, kana, romaji);
+                println!();
+            }
+
+            print_pitch_accents(&word.kana);
+
+            print_senses(&word.sense, xrefs, languages);
+        }
+    }
+}
+
+/// Formats the kana reading, followed by its romaji when enabled.
+pub fn reading(kana: &str, romaji: bool) -> String {
+    if romaji {
+        format!("{} ({})", kana, kana.to_romaji())
+    } else {
+        kana.to_string()
+    }
+}
+
+/// Prints the text after the prefix, with the furigana of its kanji on the
+/// line above and the romaji after it when enabled.
+pub fn print_ruby(prefix: &str, text: &str, kana: &str, romaji: bool) {
+    let (top, bottom) = ruby(&furigana::align(text, kana));
+
+    println!("{}{}", " ".repeat(width(prefix)), top.trim_end());
+
+    if romaji {
+        println!("{}{} ({})", prefix, bottom.trim_end(), kana.to_romaji());
+    } else {
+        println!("{}{}", prefix, bottom.trim_end());
+    }
+}
+
+/// Lays out the segments as the line of the readings and the line of the
+/// text, centering each reading on its kanji.
+fn ruby(segments: &[Segment]) -> (String, String) {
+    let mut top = String::new();
+    let mut bottom = String::new();
+
+    for segment in segments {
+        let reading = segment.reading.as_deref().unwrap_or("");
+        let columns = width(&segment.text).max(width(reading));
+
+        top.push_str(&center(reading, columns));
+        bottom.push_str(&center(&segment.text, columns));
+    }
+
+    (top, bottom)
+}
+
+fn center(text: &str, columns: usize) -> String {
+    let padding = columns - width(text);
+    let left = padding / 2;
+
+    format!("{}{}{}", " ".repeat(left), text, " ".repeat(padding - left))
+}
+
+/// Number of terminal columns of the text, the Japanese characters taking
+/// two columns.
+fn width(text: &str) -> usize {
+    text.chars()
+        .map(|c| {
+            if c.is_ascii() || c.is_half_width_katakana() {
+                1
+            } else {
+                2
+            }
+        })
+        .sum()
+}
+
+/// Prints the readings with their pitch accent, each pattern in the downstep
+/// notation (e.g. ［2］) with the high morae overlined on the line above and
+/// the drop marked with `\`.
+pub fn print_pitch_accents(kana: &[Kana]) {
+    let readings = kana
+        .iter()
+        .filter(|kana| !kana.pitch_accents.is_empty())
+        .collect::<Vec<&Kana>>();
+
+    if readings.is_empty() {
+        return;
+    }
+
+    println!("   Pitch accent:");
+
+    for kana in readings {
+        for pattern in &kana.pitch_accents {
+            println!("   {}", pitch_overline(&kana.text, *pattern).trim_end());
+            println!("   {} ［{}］", kana.text, pattern);
+        }
+    }
+
+    println!();
+}
+
+/// Line above the kana with the high morae overlined, the last one before the
+/// drop of the pitch ending with `\`.
+fn pitch_overline(kana: &str, pattern: u8) -> String {
+    let pattern = pattern as usize;
+
+    kana.morae()
+        .iter()
+        .enumerate()
+        .map(|(i, mora)| {
+            let columns = width(mora);
+            let high = match pattern {
+                0 => i > 0,
+                1 => i == 0,
+                _ => i > 0 && i < pattern,
+            };
+
+            if !high {
+                " ".repeat(columns)
+            } else if i + 1 == pattern {
+                format!("{}\\", "‾".repeat(columns - 1))
+            } else {
+                "‾".repeat(columns)
+            }
+        })
+        .collect()
+}
+
+pub fn print_examples(examples: &[Example]) {
+    if examples.is_empty() {
+        return;
+    }
+
+    println!("   Examples:");
+
+    for example in examples {
+        println!("   {}", example.japanese);
+
+        if let Some(translation) = &example.translation {
+            println!("   {}", translation);
+        }
+    }
+
+    println!();
+}
+
+/// Prints a line for each kanji of a word, e.g. for 図書館.
+pub fn print_kanji_breakdown(kanji: &[&Kanji], languages: &[Language3Letter]) {
+    if kanji.is_empty() {
+        return;
+    }
+
+    println!("   Kanji:");
+
+    for k in kanji {
+        println!("   {}", kanji_entry(k, languages));
+    }
+
+    println!();
+}
+
+/// Formats the kanji with its readings, first meanings, grade, JLPT level and
+/// number of strokes.
+pub fn kanji_entry(kanji: &Kanji, languages: &[Language3Letter]) -> String {
+    let readings = [
+        kanji.on_readings().join("、"),
+        kanji.kun_readings().join("、"),
+    ]
+    .into_iter()
+    .filter(|readings| !readings.is_empty())
+    .collect::<Vec<String>>()
+    .join(" ・ ");
+
+    let meanings = kanji.meanings(languages);
+    let meanings = meanings[..meanings.len().min(3)].join(", ");
+
+    format!(
+        "{}  {}  {}  ({})",
+        kanji.literal,
+        readings,
+        meanings,
+        kanji_details(kanji)
+    )
+}
+
+/// Prints the kanji after the prefix, with its readings, meanings and details
+/// on the lines below.
+pub fn print_kanji(prefix: &str, kanji: &Kanji, languages: &[Language3Letter]) {
+    println!("{}{}\n", prefix, kanji.literal);
+
+    for (label, readings) in [("On", kanji.on_readings()), ("Kun", kanji.kun_readings())] {
+        if !readings.is_empty() {
+            println!("   {}: {}", label, readings.join("、"));
+        }
+    }
+
+    println!("   {}", kanji.meanings(languages).join(", "));
+    println!("   {}\n", kanji_details(kanji));
+}
+
+fn kanji_details(kanji: &Kanji) -> String {
+    [
+        kanji.misc.grade.map(|grade| format!("grade {}", grade)),
+        kanji.misc.jlpt_level.map(|level| format!("JLPT {}", level)),
+        kanji
+            .misc
+            .stroke_counts
+            .first()
+            .map(|strokes| format!("{} strokes", strokes)),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<String>>()
+    .join(" · ")
+}
+
+/// Cross-references of a sense, with the word they point to when found.
+#[derive(Default)]
+pub struct SenseXrefs<'a> {
+    pub related: Vec<(&'a Xref, Option<&'a Word>)>,
+    pub antonyms: Vec<(&'a Xref, Option<&'a Word>)>,
+}
+
+/// Cross-references of each sense of the word, resolved to the word they
+/// point to.
+pub fn sense_xrefs<'a>(
+    word: &'a Word,
+    resolve: impl Fn(&Xref) -> Option<&'a Word>,
+) -> Vec<SenseXrefs<'a>> {
+    word.sense
+        .iter()
+        .map(|sense| SenseXrefs {
+            related: sense.related.iter().map(|x| (x, resolve(x))).collect(),
+            antonyms: sense.antonym.iter().map(|x| (x, resolve(x))).collect(),
+        })
+        .collect()
+}
+
+/// Formats the cross-references, with the first gloss of the sense (or of the
+/// word) they point to when found.
+fn xref_entries(xrefs: &[(&Xref, Option<&Word>)], languages: &[Language3Letter]) -> String {
+    xrefs
+        .iter()
+        .map(|(xref, word)| {
+            let (text, reading, index) = xref.parts();
+
+            let mut entry = match reading {
+                Some(reading) => format!("{}【{}】", text, reading),
+                None => text.to_string(),
+            };
+
+            let sense = word.and_then(|word| {
+                let index = index.map_or(0, |i| i.saturating_sub(1) as usize);
+                word.sense.get(index)
+            });
+
+            if let Some(gloss) = sense.and_then(|sense| sense.glosses(languages).first().copied()) {
+                entry = format!("{} ({})", entry, gloss.text);
+            }
+
+            entry
+        })
+        .collect::<Vec<String>>()
+        .join("; ")
+}
+
+/// Prints the senses with their glosses, and the cross-references of each
+/// sense (e.g. "See also") when given.
+pub fn print_senses(senses: &[Sense], xrefs: &[SenseXrefs], languages: &[Language3Letter]) {
+    let text = senses
+        .iter()
+        .enumerate()
+        .filter(|(_, s)| !s.glosses(languages).is_empty())
+        .enumerate()
+        .map(|(n, (i, s))| {
+            let mut entry = format!(
+                "   {}\n{}. {}",
+                s.part_of_speech.join(", "),
+                n + 1,
+                s.glosses(languages)
+                    .iter()
+                    .map(|g| g.text.clone())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            );
+
+            if let Some(xrefs) = xrefs.get(i) {
+                for (label, refs) in [("See also", &xrefs.related), ("Antonym", &xrefs.antonyms)] {
+                    if !refs.is_empty() {
+                        entry.push_str(&format!(
+                            "\n   {}: {}",
+                            label,
+                            xref_entries(refs, languages)
+                        ));
+                    }
+                }
+            }
+
+            entry
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    for line in text.lines() {
+        println!("   {}", line);
+    }
+
+    println!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ruby() {
+        let (top, bottom) = ruby(&furigana::align("食べる", "たべる"));
+
+        assert_eq!(top, "た    ");
+        assert_eq!(bottom, "食べる");
+
+        let (top, bottom) = ruby(&furigana::align("お茶", "おちゃ"));
+
+        assert_eq!(top, "  ちゃ");
+        assert_eq!(bottom, "お 茶 ");
+    }
+
+    #[test]
+    fn test_pitch_overline() {
+        // heiban, atamadaka, nakadaka and odaka
+        assert_eq!(pitch_overline("にほん", 0), "  ‾‾‾‾");
+        assert_eq!(pitch_overline("はし", 1), "‾\\  ");
+        assert_eq!(pitch_overline("たべる", 2), "  ‾\\  ");
+        assert_eq!(pitch_overline("おとうと", 4), "  ‾‾‾‾‾\\");
+
+        assert_eq!(pitch_overline("きょう", 1), "‾‾‾\\  ");
     }
 }